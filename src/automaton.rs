@@ -0,0 +1,342 @@
+use std::{
+    collections::{HashMap, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+};
+
+// Rectangular grid of cells, row by row
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid is not {width}x{height}");
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    // Rows have to be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        Self::new(width, height, rows.into_iter().flatten().collect())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        (row < self.height && column < self.width).then(|| &self.cells[row * self.width + column])
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.cells.iter().filter(|c| predicate(c)).count()
+    }
+}
+
+// Cells whose state a rule gets to look at, as (row, column) offsets
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Neighbourhood {
+    // the 4 orthogonal neighbours
+    VonNeumann,
+    // the 8 neighbours including diagonals
+    Moore,
+    Custom(Vec<(isize, isize)>),
+}
+
+impl Neighbourhood {
+    pub fn offsets(&self) -> Vec<(isize, isize)> {
+        match self {
+            Self::VonNeumann => vec![(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Self::Moore => (-1..=1)
+                .flat_map(|r| (-1..=1).map(move |c| (r, c)))
+                .filter(|o| *o != (0, 0))
+                .collect(),
+            Self::Custom(offsets) => offsets.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Update {
+    // every cell of a generation is computed from the previous generation
+    Synchronous,
+    // cells are updated row by row in place, so later cells see the new state of earlier ones
+    InPlace,
+}
+
+// Why a run stopped
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    // this generation is the same as the next one
+    FixedPoint { generation: usize },
+    // the generation `start + period` is the same as `start`
+    Cycle { start: usize, period: usize },
+    StepLimit,
+}
+
+// A grid where every step replaces each cell by what `rule` gives for it and its neighbours
+// inside the grid (cells beyond the edges are left out).
+pub struct Automaton<T, R> {
+    grid: Grid<T>,
+    offsets: Vec<(isize, isize)>,
+    update: Update,
+    rule: R,
+    generation: usize,
+}
+
+impl<T, R> Automaton<T, R>
+where
+    T: Clone + PartialEq + Hash,
+    R: Fn(&T, &[&T]) -> T,
+{
+    pub fn new(grid: Grid<T>, neighbourhood: Neighbourhood, update: Update, rule: R) -> Self {
+        Self {
+            grid,
+            offsets: neighbourhood.offsets(),
+            update,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    // Steps taken so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // Goes to the next generation, false if nothing changed
+    pub fn step(&mut self) -> bool {
+        let mut grid = std::mem::replace(&mut self.grid, Grid::new(0, 0, vec![]));
+        let changed = self.advance(&mut grid);
+        self.grid = grid;
+        self.generation += 1;
+        changed
+    }
+
+    // Steps until a fixed point, a cycle or `max_steps` steps
+    pub fn run(&mut self, max_steps: usize) -> Outcome {
+        self.run_with(max_steps, |_, _| {})
+    }
+
+    // Same as run, calling `observe` with every generation including the current one
+    pub fn run_with(
+        &mut self,
+        max_steps: usize,
+        mut observe: impl FnMut(usize, &Grid<T>),
+    ) -> Outcome {
+        // generations are remembered by hash and only the first one is kept: when a hash comes
+        // back, the earlier generation is replayed from it and compared in full, so a collision
+        // isn't taken for a cycle
+        let first = (self.generation, self.grid.clone());
+        let mut seen = HashMap::from([(hash(&self.grid), vec![self.generation])]);
+        observe(self.generation, &self.grid);
+
+        for _ in 0..max_steps {
+            if !self.step() {
+                return Outcome::FixedPoint {
+                    generation: self.generation - 1,
+                };
+            }
+            observe(self.generation, &self.grid);
+
+            let same_hash = seen.entry(hash(&self.grid)).or_default();
+            let start = same_hash
+                .iter()
+                .find(|generation| self.replay(&first, **generation) == self.grid);
+            if let Some(start) = start {
+                return Outcome::Cycle {
+                    start: *start,
+                    period: self.generation - start,
+                };
+            }
+            same_hash.push(self.generation);
+        }
+
+        Outcome::StepLimit
+    }
+
+    // Grid of `generation`, stepping from an earlier one
+    fn replay(&self, (from, grid): &(usize, Grid<T>), generation: usize) -> Grid<T> {
+        let mut grid = grid.clone();
+        for _ in *from..generation {
+            self.advance(&mut grid);
+        }
+        grid
+    }
+
+    // Moves `grid` to its next generation, false if nothing changed
+    fn advance(&self, grid: &mut Grid<T>) -> bool {
+        let mut changed = false;
+        match self.update {
+            Update::Synchronous => {
+                let cells = (0..grid.cells.len())
+                    .map(|i| self.next_state(grid, i))
+                    .collect::<Vec<_>>();
+                changed = cells != grid.cells;
+                grid.cells = cells;
+            }
+            Update::InPlace => {
+                for i in 0..grid.cells.len() {
+                    let state = self.next_state(grid, i);
+                    if state != grid.cells[i] {
+                        grid.cells[i] = state;
+                        changed = true;
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    fn next_state(&self, grid: &Grid<T>, i: usize) -> T {
+        let (row, column) = (i / grid.width, i % grid.width);
+        let neighbours = self
+            .offsets
+            .iter()
+            .filter_map(|(r, c)| {
+                grid.get(row.checked_add_signed(*r)?, column.checked_add_signed(*c)?)
+            })
+            .collect::<Vec<_>>();
+        (self.rule)(&grid.cells[i], &neighbours)
+    }
+}
+
+fn hash<T: Hash>(grid: &Grid<T>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    grid.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(cell: &bool, neighbours: &[&bool]) -> bool {
+        let alive = neighbours.iter().filter(|n| ***n).count();
+        alive == 3 || (*cell && alive == 2)
+    }
+
+    fn grid(rows: &str) -> Grid<bool> {
+        Grid::from_rows(
+            rows.lines()
+                .map(|l| l.chars().map(|c| c == '#').collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_life() {
+        let blinker = grid(".....\n..#..\n..#..\n..#..\n.....");
+        let mut automaton = Automaton::new(
+            blinker.clone(),
+            Neighbourhood::Moore,
+            Update::Synchronous,
+            life,
+        );
+        let mut generations = vec![];
+        let outcome = automaton.run_with(10, |generation, grid| {
+            generations.push((generation, grid.clone()));
+        });
+        assert_eq!(
+            outcome,
+            Outcome::Cycle {
+                start: 0,
+                period: 2
+            }
+        );
+        assert_eq!(generations.len(), 3);
+        assert_eq!(generations[1].1, grid(".....\n.....\n.###.\n.....\n....."));
+        assert_eq!(generations[2].1, blinker);
+
+        let block = grid("....\n.##.\n.##.\n....");
+        let mut automaton = Automaton::new(block, Neighbourhood::Moore, Update::Synchronous, life);
+        assert_eq!(automaton.run(10), Outcome::FixedPoint { generation: 0 });
+
+        let glider = grid(
+            ".#........\n..#.......\n###.......\n..........\n..........\n..........\n..........\n..........\n..........\n..........",
+        );
+        let mut automaton = Automaton::new(glider, Neighbourhood::Moore, Update::Synchronous, life);
+        assert_eq!(automaton.run(8), Outcome::StepLimit);
+        assert_eq!(automaton.generation(), 8);
+        assert_eq!(automaton.grid().count(|c| *c), 5);
+    }
+
+    #[test]
+    fn test_update_and_neighbourhoods() {
+        // a cell turns on when the one on its left is on
+        let spread = |cell: &bool, neighbours: &[&bool]| *cell || neighbours.iter().any(|n| **n);
+        let line = grid("#....");
+        let left = Neighbourhood::Custom(vec![(0, -1)]);
+
+        let mut synchronous =
+            Automaton::new(line.clone(), left.clone(), Update::Synchronous, spread);
+        synchronous.step();
+        assert_eq!(synchronous.grid(), &grid("##..."));
+        assert_eq!(synchronous.run(10), Outcome::FixedPoint { generation: 4 });
+
+        let mut in_place = Automaton::new(line, left, Update::InPlace, spread);
+        assert!(in_place.step());
+        assert_eq!(in_place.grid(), &grid("#####"));
+        assert!(!in_place.step());
+
+        let mut cross = Automaton::new(
+            grid("...\n.#.\n..."),
+            Neighbourhood::VonNeumann,
+            Update::Synchronous,
+            spread,
+        );
+        cross.step();
+        assert_eq!(cross.grid(), &grid(".#.\n###\n.#."));
+    }
+
+    #[test]
+    fn test_hash_collisions_are_not_cycles() {
+        // every grid of these hashes the same
+        #[derive(Clone, PartialEq)]
+        struct Collide(bool);
+        impl Hash for Collide {
+            fn hash<H: Hasher>(&self, _: &mut H) {}
+        }
+
+        let line = Grid::new(
+            4,
+            1,
+            vec![
+                Collide(true),
+                Collide(false),
+                Collide(false),
+                Collide(false),
+            ],
+        );
+        let spread = |cell: &Collide, neighbours: &[&Collide]| {
+            Collide(cell.0 || neighbours.iter().any(|n| n.0))
+        };
+        let mut automaton = Automaton::new(
+            line,
+            Neighbourhood::Custom(vec![(0, -1)]),
+            Update::Synchronous,
+            spread,
+        );
+        assert_eq!(automaton.run(10), Outcome::FixedPoint { generation: 3 });
+    }
+}
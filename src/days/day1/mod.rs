@@ -1,5 +1,7 @@
 // https://adventofcode.com/2025/day/1
 
+use crate::input::normalize;
+
 pub fn solution_part1(input: &str) -> usize {
     let mut pointer: i32 = 50;
     let mut zeroes = 0;
@@ -57,7 +59,7 @@ enum Dir {
 
 impl Step {
     fn from_input(input: &str) -> Vec<Self> {
-        input
+        normalize(input)
             .lines()
             .map(|line| {
                 let mut chars = line.chars();
@@ -77,6 +79,7 @@ impl Step {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
     const EXAMPLE: &str = "L68
 L30
@@ -106,6 +109,14 @@ L82";
         assert_eq!(solution_part2(EXAMPLE), 6);
     }
 
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 3);
+            assert_eq!(solution_part2(&input), 6);
+        }
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(solution_part2(INPUT), 6858);
@@ -2,6 +2,8 @@
 
 use std::{collections::HashMap, fmt::Display};
 
+use crate::input::normalize;
+
 pub fn solution_part1(input: &str) -> usize {
     let diagrams = Diagram::from_input(input);
     diagrams
@@ -35,10 +37,9 @@ struct Joltage(u128);
 
 impl Diagram {
     fn from_input(input: &str) -> Vec<Self> {
-        input
+        normalize(input)
             .trim()
             .lines()
-            .into_iter()
             .map(|l| {
                 let l: Vec<&str> = l.split(" ").collect();
                 let [indicators, buttons @ .., joltage] = &*l else {
@@ -49,9 +50,9 @@ impl Diagram {
                 let indicator_lights = IndicatorLights::from_input(indicators);
 
                 let button_wirings = buttons
-                    .into_iter()
+                    .iter()
                     .map(|b| {
-                        let b = b.trim_matches(&['(', ')']);
+                        let b = b.trim_matches(['(', ')']);
                         b.split(',').map(|b| b.parse::<usize>().unwrap()).collect()
                     })
                     .collect();
@@ -135,7 +136,7 @@ fn min_presses_joltage_recursive(
 
 impl IndicatorLights {
     fn from_input(input: &str) -> Self {
-        let indicators = input.trim_matches(&['[', ']']);
+        let indicators = input.trim_matches(['[', ']']);
         IndicatorLights(0).toggle_many(
             indicators
                 .bytes()
@@ -159,7 +160,7 @@ const JOLTAGE_BITS: usize = 12;
 const JOLTAGE_MAX_N: usize = 10;
 impl Joltage {
     fn from_input(input: &str) -> Self {
-        let joltage = input.trim_matches(&['{', '}']);
+        let joltage = input.trim_matches(['{', '}']);
         let mut size = 0;
         let joltage_bits = joltage
             .split(",")
@@ -211,6 +212,7 @@ impl Display for Joltage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
     const EXAMPLE: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
 [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
@@ -284,6 +286,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 7);
+        }
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(solution_part2(INPUT), 0);
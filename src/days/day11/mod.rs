@@ -2,13 +2,17 @@
 
 use std::{borrow::Cow, collections::HashMap};
 
+use crate::input::normalize;
+
 pub fn solution_part1(input: &str) -> usize {
-    let server_rack = ServerRack::from_input(input);
+    let input = normalize(input);
+    let server_rack = ServerRack::from_input(&input);
     count_paths(&server_rack, "you")
 }
 
 pub fn solution_part2(input: &str) -> usize {
-    let server_rack = ServerRack::from_input(input);
+    let input = normalize(input);
+    let server_rack = ServerRack::from_input(&input);
     count_paths_including(
         &server_rack,
         "svr",
@@ -31,8 +35,8 @@ fn count_paths_including<'a>(
     server_rack: &ServerRack<'a>,
     device: &'a str,
     mandatory_devices: &'a HashMap<&'a str, usize>,
-    mut mandatory_devices_found: Cow<'a, Vec<bool>>,
-    memo: &mut HashMap<(&'a str, Cow<'a, Vec<bool>>), usize>,
+    mut mandatory_devices_found: Cow<'a, [bool]>,
+    memo: &mut HashMap<(&'a str, Cow<'a, [bool]>), usize>,
 ) -> usize {
     if device == "out" {
         return (mandatory_devices.len() == mandatory_devices_found.iter().filter(|d| **d).count())
@@ -44,7 +48,7 @@ fn count_paths_including<'a>(
     }
 
     mandatory_devices_found = if let Some(index) = mandatory_devices.get(device) {
-        let mut cloned = mandatory_devices_found.as_slice().to_vec();
+        let mut cloned = mandatory_devices_found.to_vec();
         cloned[*index] = true;
         Cow::Owned(cloned)
     } else {
@@ -91,6 +95,7 @@ impl<'a> ServerRack<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
     const EXAMPLE: &str = "aaa: you hhh
 you: bbb ccc
@@ -134,6 +139,16 @@ hhh: out";
         assert_eq!(solution_part2(EXAMPLE_P2), 2);
     }
 
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 5);
+        }
+        for input in variants(EXAMPLE_P2) {
+            assert_eq!(solution_part2(&input), 2);
+        }
+    }
+
     #[test]
     fn test_part2_real() {
         assert_eq!(solution_part2(INPUT), 316291887968000);
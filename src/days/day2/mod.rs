@@ -1,5 +1,7 @@
 // https://adventofcode.com/2025/day/2
 
+use crate::input::normalize;
+
 pub fn solution_part1(input: &str) -> usize {
     let ranges = Range::from_input(input);
     let mut total_invalid = 0;
@@ -48,7 +50,7 @@ fn digits(i: usize) -> usize {
 }
 
 fn chunk_compare(mut n: usize, digits: usize, digits_to_compare: usize) -> bool {
-    if digits % digits_to_compare != 0 {
+    if !digits.is_multiple_of(digits_to_compare) {
         return false;
     }
 
@@ -74,7 +76,7 @@ struct Range {
 
 impl Range {
     fn from_input(input: &str) -> Vec<Self> {
-        input
+        normalize(input)
             .split(',')
             .map(|r| {
                 let (left, right) = r.trim().split_once('-').unwrap();
@@ -92,6 +94,7 @@ impl Range {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
     const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
 
@@ -112,6 +115,14 @@ mod tests {
         assert_eq!(solution_part2(EXAMPLE), 4174379265);
     }
 
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 1227775554);
+            assert_eq!(solution_part2(&input), 4174379265);
+        }
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(solution_part2(INPUT), 34284458938);
@@ -1,5 +1,7 @@
 // https://adventofcode.com/2025/day/3
 
+use crate::input::normalize;
+
 pub fn solution_part1(input: &str) -> usize {
     let batteries = Battery::from_input(input);
     batteries.into_iter().map(|b| b.max_joltage(2)).sum()
@@ -16,7 +18,7 @@ struct Battery {
 
 impl Battery {
     fn from_input(input: &str) -> Vec<Self> {
-        input
+        normalize(input)
             .trim()
             .lines()
             .map(|b| Self {
@@ -55,6 +57,7 @@ impl Battery {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
     const EXAMPLE: &str = "987654321111111
 811111111111119
@@ -78,6 +81,14 @@ mod tests {
         assert_eq!(solution_part2(EXAMPLE), 3121910778619);
     }
 
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 357);
+            assert_eq!(solution_part2(&input), 3121910778619);
+        }
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(solution_part2(INPUT), 172681562473501);
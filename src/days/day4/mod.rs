@@ -1,80 +1,136 @@
 // https://adventofcode.com/2025/day/4
 
+use crate::{
+    automaton::{Automaton, Grid, Neighbourhood, Update},
+    input::normalize,
+};
+
 pub fn solution_part1(input: &str) -> usize {
-    let map = input
-        .lines()
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+    let mut automaton = paper_rolls(input);
+    let rolls = automaton.grid().count(|c| *c == '@');
+    automaton.step();
+    rolls - automaton.grid().count(|c| *c == '@')
+}
 
-    let mut rolls = 0;
-    for i in 0..map.len() {
-        let line = &map[i];
-        for y in 0..line.len() {
-            if map[i][y] != '@' {
-                continue;
-            }
+// Instead of stepping the automaton until nothing changes, which rescans the whole map for every
+// wave of removals, keep how many rolls are around each roll and only look again at the
+// neighbours of the ones removed
+pub fn solution_part2(input: &str) -> usize {
+    let mut rolls = Rolls::from_input(input);
+    let mut to_remove = (0..rolls.neighbours.len())
+        .filter(|i| rolls.contains(*i) && rolls.neighbours[*i] < 4)
+        .collect::<Vec<_>>();
 
-            let how_many = (i != 0 && map[i - 1][y] == '@') as usize
-                + (i != 0 && y != 0 && map[i - 1][y - 1] == '@') as usize
-                + (y != 0 && map[i][y - 1] == '@') as usize
-                + (y != 0 && i != map.len() - 1 && map[i + 1][y - 1] == '@') as usize
-                + (i != map.len() - 1 && map[i + 1][y] == '@') as usize
-                + (i != map.len() - 1 && y != line.len() - 1 && map[i + 1][y + 1] == '@') as usize
-                + (y != line.len() - 1 && map[i][y + 1] == '@') as usize
-                + (y != line.len() - 1 && i != 0 && map[i - 1][y + 1] == '@') as usize;
-            if how_many < 4 {
-                rolls += 1;
+    let mut removed = 0;
+    while let Some(i) = to_remove.pop() {
+        rolls.remove(i);
+        removed += 1;
+        for j in rolls.around(i) {
+            if rolls.contains(j) {
+                rolls.neighbours[j] -= 1;
+                // a roll goes in the worklist once, when it gets under 4 neighbours
+                if rolls.neighbours[j] == 3 {
+                    to_remove.push(j);
+                }
             }
         }
     }
 
-    rolls
+    removed
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let mut map = input
-        .lines()
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let mut rolls = 0;
-    loop {
-        let mut to_add = 0;
-        for i in 0..map.len() {
-            for y in 0..map[i].len() {
-                if map[i][y] != '@' {
-                    continue;
-                }
+// Rolls of paper packed a bit per cell, with an empty border around the map so every cell of the
+// map has its 8 neighbours
+struct Rolls {
+    bits: Vec<u64>,
+    width: usize,
+    // how many rolls are around each cell
+    neighbours: Vec<u8>,
+}
 
-                let how_many = (i != 0 && map[i - 1][y] == '@') as usize
-                    + (i != 0 && y != 0 && map[i - 1][y - 1] == '@') as usize
-                    + (y != 0 && map[i][y - 1] == '@') as usize
-                    + (y != 0 && i != map.len() - 1 && map[i + 1][y - 1] == '@') as usize
-                    + (i != map.len() - 1 && map[i + 1][y] == '@') as usize
-                    + (i != map.len() - 1 && y != map[i].len() - 1 && map[i + 1][y + 1] == '@')
-                        as usize
-                    + (y != map[i].len() - 1 && map[i][y + 1] == '@') as usize
-                    + (y != map[i].len() - 1 && i != 0 && map[i - 1][y + 1] == '@') as usize;
-                if how_many < 4 {
-                    map[i][y] = 'x';
-                    to_add += 1;
+impl Rolls {
+    fn from_input(input: &str) -> Self {
+        let input = normalize(input);
+        let lines = input.lines().collect::<Vec<_>>();
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0) + 2;
+        let cells = width * (lines.len() + 2);
+
+        let mut rolls = Self {
+            bits: vec![0; cells.div_ceil(64)],
+            width,
+            neighbours: vec![0; cells],
+        };
+        for (row, line) in lines.iter().enumerate() {
+            for (column, c) in line.bytes().enumerate() {
+                if c == b'@' {
+                    let i = (row + 1) * width + column + 1;
+                    rolls.bits[i / 64] |= 1 << (i % 64);
+                    for j in rolls.around(i) {
+                        rolls.neighbours[j] += 1;
+                    }
                 }
             }
         }
+        rolls
+    }
 
-        if to_add == 0 {
-            break;
-        } else {
-            rolls += to_add;
-        }
+    fn contains(&self, i: usize) -> bool {
+        self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.bits[i / 64] &= !(1 << (i % 64));
     }
 
-    rolls
+    fn around(&self, i: usize) -> [usize; 8] {
+        let w = self.width;
+        [
+            i - w - 1,
+            i - w,
+            i - w + 1,
+            i - 1,
+            i + 1,
+            i + w - 1,
+            i + w,
+            i + w + 1,
+        ]
+    }
+}
+
+// Every step, the rolls of paper with fewer than 4 rolls around them can be reached by a forklift
+// and get removed, becoming an `x`
+fn paper_rolls(input: &str) -> Automaton<char, impl Fn(&char, &[&char]) -> char> {
+    // short lines are padded with empty space, the grid has to be a rectangle
+    let input = normalize(input);
+    let width = input.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let map = input
+        .lines()
+        .map(|l| {
+            let mut row = l.chars().collect::<Vec<_>>();
+            row.resize(width, '.');
+            row
+        })
+        .collect::<Vec<_>>();
+
+    Automaton::new(
+        Grid::from_rows(map),
+        Neighbourhood::Moore,
+        Update::Synchronous,
+        |cell: &char, neighbours: &[&char]| {
+            let how_many = neighbours.iter().filter(|n| ***n == '@').count();
+            if *cell == '@' && how_many < 4 {
+                'x'
+            } else {
+                *cell
+            }
+        },
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
     const EXAMPLE: &str = "..@@.@@@@.
 @@@.@.@.@@
@@ -99,11 +155,49 @@ mod tests {
         assert_eq!(solution_part1(INPUT), 1523);
     }
 
+    #[test]
+    fn test_part1_ragged() {
+        assert_eq!(solution_part1("@@@\n@\n@@"), 5);
+    }
+
     #[test]
     fn test_part2_example() {
         assert_eq!(solution_part2(EXAMPLE), 43);
     }
 
+    #[test]
+    fn test_part2_matches_automaton() {
+        // pseudo-random map, with ragged lines
+        let mut seed = 1u64;
+        let map = (0..80)
+            .map(|row| {
+                (0..120 - row % 7)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                        if seed >> 62 == 0 { '.' } else { '@' }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut automaton = paper_rolls(&map);
+        let rolls = automaton.grid().count(|c| *c == '@');
+        automaton.run(usize::MAX);
+        let removed = rolls - automaton.grid().count(|c| *c == '@');
+
+        assert!(removed > 0);
+        assert_eq!(solution_part2(&map), removed);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 13);
+            assert_eq!(solution_part2(&input), 43);
+        }
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(solution_part2(INPUT), 9290);
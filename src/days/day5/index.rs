@@ -0,0 +1,196 @@
+use super::Range;
+
+// Fresh ranges merged into disjoint ones to answer queries by binary search, keeping the
+// original ranges around to tell which of them an id comes from
+pub struct FreshIndex {
+    merged: Vec<Range>,
+    // sorted by start
+    ranges: Vec<Range>,
+    // largest end of ranges[..=i], to stop looking for the ranges of an id early
+    max_end: Vec<usize>,
+}
+
+impl FreshIndex {
+    pub fn new(ranges: &[Range]) -> Self {
+        let mut ranges = ranges.to_vec();
+        ranges.sort();
+
+        let mut merged: Vec<Range> = vec![];
+        for range in &ranges {
+            match merged.last_mut() {
+                // touching ranges are merged too, there is no id between them
+                Some(last) if range.start <= last.end.saturating_add(1) => {
+                    last.end = last.end.max(range.end);
+                }
+                _ => merged.push(*range),
+            }
+        }
+
+        let max_end = ranges
+            .iter()
+            .scan(0, |max, range| {
+                *max = range.end.max(*max);
+                Some(*max)
+            })
+            .collect();
+
+        Self {
+            merged,
+            ranges,
+            max_end,
+        }
+    }
+
+    // Disjoint and sorted, with a gap of at least one id between two of them
+    pub fn merged(&self) -> &[Range] {
+        &self.merged
+    }
+
+    pub fn is_fresh(&self, id: usize) -> bool {
+        let i = self.merged.partition_point(|r| r.start <= id);
+        i > 0 && self.merged[i - 1].contains(id)
+    }
+
+    // Original ranges containing `id`, by start
+    pub fn ranges_containing(&self, id: usize) -> Vec<Range> {
+        let mut containing = vec![];
+        let mut i = self.ranges.partition_point(|r| r.start <= id);
+        while i > 0 && self.max_end[i - 1] >= id {
+            i -= 1;
+            if self.ranges[i].contains(id) {
+                containing.push(self.ranges[i]);
+            }
+        }
+        containing.reverse();
+        containing
+    }
+
+    pub fn count_fresh(&self, ids: impl IntoIterator<Item = usize>) -> usize {
+        ids.into_iter().filter(|id| self.is_fresh(*id)).count()
+    }
+
+    // Ids that aren't fresh between the smallest and the largest fresh ones
+    pub fn gaps(&self) -> Vec<Range> {
+        self.merged
+            .windows(2)
+            .map(|w| Range {
+                start: w[0].end + 1,
+                end: w[1].start - 1,
+            })
+            .collect()
+    }
+
+    // The gap with the most ids in it, the first one on ties
+    pub fn largest_gap(&self) -> Option<Range> {
+        self.gaps()
+            .into_iter()
+            .rev()
+            .max_by_key(|gap| gap.end - gap.start)
+    }
+
+    // Original ranges contained in another one, so that dropping them changes nothing.
+    // Of two identical ranges only the second one is redundant.
+    pub fn redundant(&self) -> Vec<Range> {
+        let mut redundant = vec![];
+        for (i, range) in self.ranges.iter().enumerate() {
+            // ranges before start earlier or at the same place, and the last one starting at the
+            // same place ends the furthest
+            let before = i > 0 && self.max_end[i - 1] >= range.end;
+            let same_start = self.ranges[i..].partition_point(|r| r.start == range.start);
+            let after = self.ranges[i + same_start - 1].end > range.end;
+            if before || after {
+                redundant.push(*range);
+            }
+        }
+        redundant
+    }
+
+    // The merged ranges written back as the ranges section of an input, one `a-b` per line
+    pub fn to_input(&self) -> String {
+        self.merged
+            .iter()
+            .map(|r| format!("{}-{}", r.start, r.end))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day5::{Database, tests::EXAMPLE};
+
+    #[test]
+    fn test_index_example() {
+        let database = Database::from_input(EXAMPLE);
+        let index = database.index();
+
+        assert_eq!(
+            index.merged(),
+            [Range { start: 3, end: 5 }, Range { start: 10, end: 20 }]
+        );
+        let fresh = (0..25).filter(|id| index.is_fresh(*id)).collect::<Vec<_>>();
+        assert_eq!(fresh, [3, 4, 5, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20]);
+        assert_eq!(index.count_fresh([1, 5, 8, 11, 17, 32]), 3);
+
+        assert_eq!(
+            index.ranges_containing(17),
+            [Range { start: 12, end: 18 }, Range { start: 16, end: 20 }]
+        );
+        assert_eq!(index.ranges_containing(4), [Range { start: 3, end: 5 }]);
+        assert_eq!(index.ranges_containing(8), []);
+
+        // the database is still there
+        assert_eq!(database.how_many_fresh(), 3);
+        assert_eq!(database.how_many_possible_fresh(), 14);
+    }
+
+    #[test]
+    fn test_coverage_example() {
+        let database = Database::from_input(EXAMPLE);
+        let index = database.index();
+
+        assert_eq!(index.gaps(), [Range { start: 6, end: 9 }]);
+        assert_eq!(index.largest_gap(), Some(Range { start: 6, end: 9 }));
+        assert_eq!(index.redundant(), []);
+        assert_eq!(index.to_input(), "3-5\n10-20");
+    }
+
+    #[test]
+    fn test_coverage() {
+        let input = "1-2\n30-40\n5-8\n32-35\n9-10\n5-8\n14-17\n30-31\n\n1";
+        let database = Database::from_input(input);
+        let index = database.index();
+
+        // 5-8 and 9-10 touch, so there's no gap between them
+        assert_eq!(
+            index.gaps(),
+            [
+                Range { start: 3, end: 4 },
+                Range { start: 11, end: 13 },
+                Range { start: 18, end: 29 }
+            ]
+        );
+        assert_eq!(index.largest_gap(), Some(Range { start: 18, end: 29 }));
+        assert_eq!(
+            index.redundant(),
+            [
+                Range { start: 5, end: 8 },
+                Range { start: 30, end: 31 },
+                Range { start: 32, end: 35 }
+            ]
+        );
+
+        let compacted = index.to_input();
+        assert_eq!(compacted, "1-2\n5-10\n14-17\n30-40");
+        let compacted = Database::from_input(&format!("{compacted}\n\n1"));
+        assert_eq!(
+            compacted.how_many_possible_fresh(),
+            database.how_many_possible_fresh()
+        );
+
+        let ties = Database::from_input("1-1\n7-7\n4-4\n\n1");
+        assert_eq!(ties.index().largest_gap(), Some(Range { start: 2, end: 3 }));
+        assert_eq!(FreshIndex::new(&[]).largest_gap(), None);
+    }
+}
@@ -1,5 +1,12 @@
 // https://adventofcode.com/2025/day/5
 
+pub mod index;
+pub mod stream;
+
+use index::FreshIndex;
+
+use crate::input::normalize;
+
 pub fn solution_part1(input: &str) -> usize {
     Database::from_input(input).how_many_fresh()
 }
@@ -8,88 +15,54 @@ pub fn solution_part2(input: &str) -> usize {
     Database::from_input(input).how_many_possible_fresh()
 }
 
-struct Database {
-    fresh_ids_ranges: Vec<Range>,
+pub struct Database {
+    index: FreshIndex,
     ids: Vec<usize>,
 }
 
-#[derive(PartialEq, Eq, Ord, Debug)]
-struct Range {
-    start: usize,
-    end: usize,
+// Inclusive range of fresh ids, ordered by start first and by end only if starts are equal
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Range {
+    pub start: usize,
+    pub end: usize,
 }
 
 impl Database {
-    fn from_input(input: &str) -> Self {
-        let (ranges, ids) = input.split_once("\n\n").unwrap();
-        let mut ranges = ranges
+    pub fn from_input(input: &str) -> Self {
+        let input = normalize(input);
+        // without ids, there's nothing left of the blank line after the ranges
+        let (ranges, ids) = input.split_once("\n\n").unwrap_or((&input, ""));
+        let ranges = ranges
             .trim()
             .lines()
-            .map(|r| Range::from_input(r))
+            .map(Range::from_input)
             .collect::<Vec<_>>();
-        let mut ids = ids
+        let ids = ids
             .trim()
             .lines()
             .map(|id| id.parse::<usize>().unwrap())
             .collect::<Vec<_>>();
-        ranges.sort();
-        ids.sort();
 
         Self {
-            fresh_ids_ranges: ranges,
+            index: FreshIndex::new(&ranges),
             ids,
         }
     }
 
-    fn how_many_fresh(self) -> usize {
-        let mut r_i = 0;
-        let mut id_i = 0;
-        let mut how_many = 0;
-
-        loop {
-            let range = &self.fresh_ids_ranges[r_i];
-            let id = &self.ids[id_i];
-            if range.start <= *id && *id <= range.end {
-                how_many += 1;
-                id_i += 1;
-                if id_i == self.ids.len() {
-                    break;
-                }
-            } else if range.end < *id {
-                r_i += 1;
-                if r_i == self.fresh_ids_ranges.len() {
-                    break;
-                }
-            } else {
-                id_i += 1;
-                if id_i == self.ids.len() {
-                    break;
-                }
-            }
-        }
-
-        how_many
+    pub fn index(&self) -> &FreshIndex {
+        &self.index
     }
 
-    fn how_many_possible_fresh(self) -> usize {
-        let mut how_many = 0;
-        let mut last_end = 0;
-
-        for range in &self.fresh_ids_ranges {
-            let range_size = range.end - range.start + 1;
-            let range_already_added = if last_end >= range.start {
-                last_end - range.start + 1
-            } else {
-                0
-            };
-
-            if range_already_added < range_size {
-                how_many += range_size - range_already_added;
-            }
-            last_end = last_end.max(range.end);
-        }
+    pub fn how_many_fresh(&self) -> usize {
+        self.index.count_fresh(self.ids.iter().copied())
+    }
 
-        how_many
+    pub fn how_many_possible_fresh(&self) -> usize {
+        self.index
+            .merged()
+            .iter()
+            .map(|range| range.end - range.start + 1)
+            .sum()
     }
 }
 
@@ -101,24 +74,18 @@ impl Range {
             end: end.parse::<usize>().unwrap(),
         }
     }
-}
 
-// Order by start first and by end only if start are equal
-impl PartialOrd for Range {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.start.partial_cmp(&other.start) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
-        }
-        self.end.partial_cmp(&other.end)
+    pub fn contains(&self, id: usize) -> bool {
+        self.start <= id && id <= self.end
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
-    const EXAMPLE: &str = "3-5
+    pub(super) const EXAMPLE: &str = "3-5
 10-14
 16-20
 12-18
@@ -142,11 +109,26 @@ mod tests {
         assert_eq!(solution_part1(INPUT), 874);
     }
 
+    #[test]
+    fn test_empty_sections() {
+        assert_eq!(solution_part1("3-5\n\n"), 0);
+        assert_eq!(solution_part1("\n\n1\n2"), 0);
+        assert_eq!(solution_part2("\n\n1\n2"), 0);
+    }
+
     #[test]
     fn test_part2_example() {
         assert_eq!(solution_part2(EXAMPLE), 14);
     }
 
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 3);
+            assert_eq!(solution_part2(&input), 14);
+        }
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(solution_part2(INPUT), 348548952146313);
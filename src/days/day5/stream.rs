@@ -0,0 +1,155 @@
+use std::io::{self, BufRead};
+
+use super::{Range, index::FreshIndex};
+
+// Both answers at once, see count_streaming
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StreamCounts {
+    pub fresh: usize,
+    pub possible_fresh: usize,
+}
+
+// Same counts as solution_part1 and solution_part2 for inputs too big to load. Only the ranges
+// are kept in memory: the ids are checked one at a time as they're read, in any order.
+pub fn count_streaming<R: BufRead>(mut reader: R) -> io::Result<StreamCounts> {
+    let mut line = String::new();
+    let mut ranges = vec![];
+    let mut first_line = true;
+    while reader.read_line(&mut line)? > 0 {
+        // a byte order mark can only start the first line, the rest of normalize is done by trim
+        let range = if first_line {
+            line.strip_prefix('\u{feff}').unwrap_or(&line)
+        } else {
+            &line
+        }
+        .trim();
+        if !range.is_empty() {
+            ranges.push(parse_range(range)?);
+        } else if !first_line {
+            // the first blank line ends the ranges, even with none before it, as in from_input
+            // splitting on "\n\n": a blank first line is only a line break before the ranges
+            break;
+        }
+        first_line = false;
+        line.clear();
+    }
+
+    let index = FreshIndex::new(&ranges);
+    drop(ranges);
+
+    let mut fresh = 0;
+    line.clear();
+    while reader.read_line(&mut line)? > 0 {
+        let id = line.trim();
+        if !id.is_empty() {
+            let id = id.parse::<usize>().map_err(|e| invalid(id, e))?;
+            if index.is_fresh(id) {
+                fresh += 1;
+            }
+        }
+        line.clear();
+    }
+
+    Ok(StreamCounts {
+        fresh,
+        possible_fresh: index.merged().iter().map(|r| r.end - r.start + 1).sum(),
+    })
+}
+
+fn parse_range(range: &str) -> io::Result<Range> {
+    let (start, end) = range
+        .split_once('-')
+        .ok_or_else(|| invalid(range, "missing `-`"))?;
+    Ok(Range {
+        start: start.parse().map_err(|e| invalid(range, e))?,
+        end: end.parse().map_err(|e| invalid(range, e))?,
+    })
+}
+
+fn invalid(line: &str, error: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("`{line}`: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day5::{solution_part1, solution_part2, tests::EXAMPLE};
+
+    #[test]
+    fn test_streaming_matches_in_memory() {
+        assert_eq!(
+            count_streaming(EXAMPLE.as_bytes()).unwrap(),
+            StreamCounts {
+                fresh: 3,
+                possible_fresh: 14
+            }
+        );
+
+        for input in crate::input::variants(EXAMPLE) {
+            assert_eq!(
+                count_streaming(input.as_bytes()).unwrap(),
+                count_streaming(EXAMPLE.as_bytes()).unwrap()
+            );
+        }
+
+        // pseudo-random overlapping ranges and unsorted ids
+        let mut seed = 42u64;
+        let mut next = |bound: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % bound
+        };
+        let mut input = String::new();
+        for _ in 0..200 {
+            let start = next(100_000);
+            input += &format!("{}-{}\n", start, start + next(2_000));
+        }
+        input += "\n";
+        for _ in 0..2_000 {
+            input += &format!("{}\n", next(110_000));
+        }
+
+        // ranges starting at 0, and sections that are empty or start with blank lines
+        for input in [
+            input.as_str(),
+            "0-5\n\n1",
+            "0-0\n3-4\n\n0\n2",
+            "2-3\n0-2\n\n0",
+            "\n\n1\n2",
+            "\n3-5\n\n4",
+            "3-5\n\n",
+        ] {
+            let counts =
+                count_streaming(io::BufReader::with_capacity(64, input.as_bytes())).unwrap();
+            assert_eq!(counts.fresh, solution_part1(input));
+            assert_eq!(counts.possible_fresh, solution_part2(input));
+        }
+        assert_eq!(solution_part2("0-5\n\n1"), 6);
+    }
+
+    #[test]
+    fn test_streaming_byte_order_mark() {
+        // read a byte at a time, so the mark is split across reads
+        let input = "\u{feff}3-5\n\n4\n\u{feff}5";
+        let counts = count_streaming(io::BufReader::with_capacity(1, input.as_bytes()));
+        assert!(counts.unwrap_err().to_string().starts_with("`\u{feff}5`"));
+
+        let input = "\u{feff}3-5\n\n4";
+        let counts = count_streaming(io::BufReader::with_capacity(1, input.as_bytes()));
+        assert_eq!(
+            counts.unwrap(),
+            StreamCounts {
+                fresh: 1,
+                possible_fresh: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_streaming_errors() {
+        let error = count_streaming("3-5\n10\n\n4".as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("`10`"));
+    }
+}
@@ -0,0 +1,122 @@
+use std::ops::Range;
+
+use super::{MathWorksheet, WorksheetError};
+
+// How a single problem was read and solved, to find which one was misread
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Explanation {
+    // e.g. `123 * 45 * 6`
+    pub expression: String,
+    pub value: i128,
+    // columns of the input the problem is written in
+    pub columns: Range<usize>,
+    // total of this problem and every one before it
+    pub running_total: i128,
+}
+
+impl MathWorksheet {
+    pub fn explain(&self) -> Result<Vec<Explanation>, WorksheetError> {
+        let mut running_total = 0i128;
+        self.problems
+            .iter()
+            .map(|problem| {
+                let value = problem.solve()?;
+                running_total =
+                    running_total
+                        .checked_add(value)
+                        .ok_or(WorksheetError::Arithmetic {
+                            column: problem.column,
+                        })?;
+                Ok(Explanation {
+                    expression: problem
+                        .numbers
+                        .iter()
+                        .map(|n| n.to_string())
+                        .collect::<Vec<_>>()
+                        .join(&format!(" {} ", problem.operator)),
+                    value,
+                    columns: problem.columns.clone(),
+                    running_total,
+                })
+            })
+            .collect()
+    }
+}
+
+// The explanations as a table, a problem per line with the numbers aligned to the right
+pub fn table(explanations: &[Explanation]) -> String {
+    let header = ["columns", "expression", "value", "total"].map(String::from);
+    let rows = explanations
+        .iter()
+        .map(|e| {
+            [
+                format!("{}..{}", e.columns.start, e.columns.end),
+                e.expression.clone(),
+                e.value.to_string(),
+                e.running_total.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = header.clone().map(|h| h.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut lines = vec![];
+    for row in [header].iter().chain(&rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| match i {
+                0 | 1 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day6::{OperatorTable, notation::Notation, tests::EXAMPLE};
+
+    #[test]
+    fn test_explain_example() {
+        let operators = OperatorTable::default();
+        let worksheet = MathWorksheet::parse(EXAMPLE, Notation::Human, &operators).unwrap();
+        let explanations = worksheet.explain().unwrap();
+
+        assert_eq!(
+            explanations[0],
+            Explanation {
+                expression: "123 * 45 * 6".to_string(),
+                value: 33210,
+                columns: 0..3,
+                running_total: 33210,
+            }
+        );
+        assert_eq!(explanations[2].columns, 8..11);
+        assert_eq!(explanations[3].running_total, 4277556);
+
+        assert_eq!(
+            table(&explanations),
+            "columns | expression     |   value |   total
+0..3    | 123 * 45 * 6   |   33210 |   33210
+4..7    | 328 + 64 + 98  |     490 |   33700
+8..11   | 51 * 387 * 215 | 4243455 | 4277155
+12..15  | 64 + 23 + 314  |     401 | 4277556"
+        );
+
+        let worksheet = MathWorksheet::parse(EXAMPLE, Notation::Cephalopod, &operators).unwrap();
+        let explanations = worksheet.explain().unwrap();
+        assert_eq!(explanations[3].expression, "4 + 431 + 623");
+        assert_eq!(explanations[3].running_total, 3263827);
+    }
+}
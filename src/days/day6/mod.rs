@@ -1,100 +1,329 @@
 // https://adventofcode.com/2025/day/6
 
+pub mod explain;
+pub mod notation;
+
+use std::{fmt::Display, ops::Range};
+
+use notation::Notation;
+
+use crate::input::normalize;
+
 pub fn solution_part1(input: &str) -> usize {
-    MathWorksheet::from_input(input).solve()
+    let total = solution_part1_with(input, &OperatorTable::default()).unwrap();
+    usize::try_from(total).unwrap()
 }
 
 pub fn solution_part2(input: &str) -> usize {
-    MathWorksheet::from_input_right_to_left(input).solve()
+    let total = solution_part2_with(input, &OperatorTable::default()).unwrap();
+    usize::try_from(total).unwrap()
+}
+
+pub fn solution_part1_with(input: &str, operators: &OperatorTable) -> Result<i128, WorksheetError> {
+    MathWorksheet::from_input(input, operators)?.solve()
+}
+
+pub fn solution_part2_with(input: &str, operators: &OperatorTable) -> Result<i128, WorksheetError> {
+    MathWorksheet::from_input_right_to_left(input, operators)?.solve()
 }
 
-struct MathWorksheet {
+#[derive(Clone, PartialEq, Debug)]
+pub struct MathWorksheet {
     problems: Vec<MathProblem>,
 }
 
 impl MathWorksheet {
-    fn from_input(input: &str) -> Self {
-        let lines: Vec<&str> = input.trim().lines().collect();
-        let operations_line = lines.last().unwrap();
-        let numbers_lines = &lines[..lines.len() - 1];
-        let operations = operations_line
-            .chars()
-            .filter(|c| *c != ' ')
-            .collect::<Vec<_>>();
-
-        let mut problems = vec![MathProblem::default(); operations.len()];
-        for (i, operation) in operations.into_iter().enumerate() {
-            problems[i].operation = operation;
-        }
-
-        for line in numbers_lines {
-            for (i, number) in line.split_whitespace().enumerate() {
-                problems[i].numbers.push(number.parse::<usize>().unwrap());
-            }
+    pub fn parse(
+        input: &str,
+        notation: Notation,
+        operators: &OperatorTable,
+    ) -> Result<Self, WorksheetError> {
+        match notation {
+            Notation::Human => Self::from_input(input, operators),
+            Notation::Cephalopod => Self::from_input_right_to_left(input, operators),
         }
-
-        Self { problems }
     }
 
-    fn from_input_right_to_left(input: &str) -> Self {
-        let lines: Vec<&str> = input.lines().collect();
-        let operations_line = lines.last().unwrap();
-        let numbers_lines = &lines[..lines.len() - 1];
-        let operations = operations_line
-            .chars()
-            .filter(|c| *c != ' ')
-            .collect::<Vec<_>>();
+    // Each line of a problem is a number
+    fn from_input(input: &str, operators: &OperatorTable) -> Result<Self, WorksheetError> {
+        let problems = blocks(input, operators)?
+            .into_iter()
+            .map(|block| {
+                let mut problem = block.problem();
+                for (line, row) in block.rows.iter().enumerate() {
+                    let number = row.iter().collect::<String>();
+                    if !number.trim().is_empty() {
+                        problem.numbers.push(block.parse(number.trim(), line)?);
+                    }
+                }
+                Ok(problem)
+            })
+            .collect::<Result<_, _>>()?;
 
-        let mut problems = vec![MathProblem::default(); operations.len()];
-        for (i, operation) in operations.into_iter().enumerate() {
-            problems[i].operation = operation;
-        }
+        Ok(Self { problems })
+    }
 
-        let numbers_lines = numbers_lines
+    // Each column of a problem is a number, read top to bottom, and the columns are read right to
+    // left
+    fn from_input_right_to_left(
+        input: &str,
+        operators: &OperatorTable,
+    ) -> Result<Self, WorksheetError> {
+        let problems = blocks(input, operators)?
             .into_iter()
-            .map(|line| line.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-
-        let max_digits = numbers_lines.len();
-        let width = numbers_lines[0].len();
-        let mut problems_index = 0;
-
-        for j in 0..width {
-            let mut n: usize = 0;
-            let mut empty_column = true;
-            for i in 0..max_digits {
-                if numbers_lines[i][j] != ' ' {
-                    n = n * 10 + numbers_lines[i][j].to_digit(10).unwrap() as usize;
-                    empty_column = false;
+            .map(|block| {
+                let mut problem = block.problem();
+                for j in (0..block.columns.len()).rev() {
+                    let number = block
+                        .rows
+                        .iter()
+                        .map(|row| row[j])
+                        .filter(|c| *c != ' ')
+                        .collect::<String>();
+                    if !number.is_empty() {
+                        let number = block.parse(&number, 0)?;
+                        problem.numbers.push(number);
+                    }
                 }
-            }
-            if empty_column {
-                problems_index += 1;
-                continue;
-            }
-            problems[problems_index].numbers.push(n);
+                Ok(problem)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { problems })
+    }
+
+    pub fn solve(&self) -> Result<i128, WorksheetError> {
+        self.problems.iter().try_fold(0i128, |total, p| {
+            let column = p.column;
+            total
+                .checked_add(p.solve()?)
+                .ok_or(WorksheetError::Arithmetic { column })
+        })
+    }
+}
+
+// The columns of a single problem: everything between two columns that are blank on every line
+struct Block {
+    columns: Range<usize>,
+    operator: String,
+    operation: Operator,
+    // column of the operator in the last line
+    operator_column: usize,
+    // the number lines cut to the block's columns
+    rows: Vec<Vec<char>>,
+}
+
+impl Block {
+    fn problem(&self) -> MathProblem {
+        MathProblem {
+            numbers: vec![],
+            operator: self.operator.clone(),
+            operation: self.operation,
+            column: self.operator_column,
+            columns: self.columns.clone(),
         }
+    }
+
+    fn parse(&self, number: &str, line: usize) -> Result<i128, WorksheetError> {
+        number.parse().map_err(|_| WorksheetError::InvalidNumber {
+            number: number.to_string(),
+            line,
+            column: self.columns.start,
+        })
+    }
+}
 
-        Self { problems }
+// Cuts the worksheet into problems. Lines don't have to be the same length, as editors like to
+// trim trailing spaces, so they're padded with spaces to the longest one first.
+fn blocks(input: &str, operators: &OperatorTable) -> Result<Vec<Block>, WorksheetError> {
+    let mut lines = normalize(input)
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    while lines
+        .last()
+        .is_some_and(|line| line.iter().all(|c| c.is_whitespace()))
+    {
+        lines.pop();
     }
+    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    for line in &mut lines {
+        line.resize(width, ' ');
+    }
+
+    let Some((operations_line, numbers_lines)) = lines.split_last() else {
+        return Ok(vec![]);
+    };
+    let blank = |j: usize| lines.iter().all(|line| line[j] == ' ');
+
+    let mut blocks = vec![];
+    let mut j = 0;
+    while j < width {
+        if blank(j) {
+            j += 1;
+            continue;
+        }
+        let start = j;
+        while j < width && !blank(j) {
+            j += 1;
+        }
 
-    fn solve(self) -> usize {
-        self.problems.into_iter().map(|p| p.solve()).sum()
+        let operator = operations_line[start..j].iter().collect::<String>();
+        let Some(offset) = operator.find(|c| c != ' ') else {
+            return Err(WorksheetError::MissingOperator { column: start });
+        };
+        let operator = operator.trim();
+        let operation = operators
+            .get(operator)
+            .ok_or_else(|| WorksheetError::UnknownOperator {
+                operator: operator.to_string(),
+                column: start + offset,
+            })?;
+
+        blocks.push(Block {
+            columns: start..j,
+            operator: operator.to_string(),
+            operation,
+            operator_column: start + offset,
+            rows: numbers_lines
+                .iter()
+                .map(|line| line[start..j].to_vec())
+                .collect(),
+        });
     }
+
+    Ok(blocks)
 }
 
-#[derive(Default, Clone)]
+#[derive(Clone, Debug)]
 struct MathProblem {
-    numbers: Vec<usize>,
-    operation: char,
+    numbers: Vec<i128>,
+    // how the operator is written, and what it does
+    operator: String,
+    operation: Operator,
+    // column of the operator in the last line
+    column: usize,
+    // columns of the input the problem is written in
+    columns: Range<usize>,
+}
+
+// Same numbers and operator, wherever they are in the worksheet
+impl PartialEq for MathProblem {
+    fn eq(&self, other: &Self) -> bool {
+        self.numbers == other.numbers && self.operator == other.operator
+    }
 }
 
 impl MathProblem {
-    fn solve(self) -> usize {
-        match self.operation {
-            '+' => self.numbers.into_iter().sum(),
-            '*' => self.numbers.into_iter().product(),
-            _ => unreachable!(),
+    fn solve(&self) -> Result<i128, WorksheetError> {
+        let column = self.column;
+        let mut numbers = self.numbers.iter().copied();
+        let first = numbers
+            .next()
+            .ok_or(WorksheetError::EmptyProblem { column })?;
+        numbers.try_fold(first, |acc, n| {
+            (self.operation.apply)(acc, n).ok_or(WorksheetError::Arithmetic { column })
+        })
+    }
+}
+
+// A worksheet operator, folding the numbers of a problem from the top down.
+// `apply` gives None when the result doesn't fit or isn't defined, like dividing by zero.
+#[derive(Clone, Copy, Debug)]
+pub struct Operator {
+    pub apply: fn(i128, i128) -> Option<i128>,
+}
+
+// Operators the worksheets can use, by how they're written in the last line
+#[derive(Clone, Debug)]
+pub struct OperatorTable {
+    operators: Vec<(String, Operator)>,
+}
+
+impl OperatorTable {
+    pub fn empty() -> Self {
+        Self { operators: vec![] }
+    }
+
+    // Adds an operator, replacing any other written the same way
+    pub fn with(mut self, name: &str, apply: fn(i128, i128) -> Option<i128>) -> Self {
+        self.operators.retain(|(n, _)| n != name);
+        self.operators.push((name.to_string(), Operator { apply }));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<Operator> {
+        self.operators
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, operator)| *operator)
+    }
+}
+
+// `+` and `*` of the puzzle, plus `-`, `/`, `%`, `min` and `max`
+impl Default for OperatorTable {
+    fn default() -> Self {
+        Self::empty()
+            .with("+", i128::checked_add)
+            .with("*", i128::checked_mul)
+            .with("-", i128::checked_sub)
+            .with("/", i128::checked_div)
+            .with("%", i128::checked_rem)
+            .with("min", |a, b| Some(a.min(b)))
+            .with("max", |a, b| Some(a.max(b)))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum WorksheetError {
+    UnknownOperator {
+        operator: String,
+        column: usize,
+    },
+    // the problem starting at this column has nothing in the last line
+    MissingOperator {
+        column: usize,
+    },
+    // on the line of the problem starting at this column, 0 when read right to left
+    InvalidNumber {
+        number: String,
+        line: usize,
+        column: usize,
+    },
+    // the problem with its operator at this column has no numbers
+    EmptyProblem {
+        column: usize,
+    },
+    // overflowing or dividing by zero in the problem with its operator at this column
+    Arithmetic {
+        column: usize,
+    },
+}
+
+impl Display for WorksheetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownOperator { operator, column } => {
+                write!(f, "unknown operator `{operator}` at column {column}")
+            }
+            Self::MissingOperator { column } => {
+                write!(f, "problem at column {column} has no operator")
+            }
+            Self::InvalidNumber {
+                number,
+                line,
+                column,
+            } => write!(
+                f,
+                "invalid number `{number}` on line {line} of the problem at column {column}"
+            ),
+            Self::EmptyProblem { column } => {
+                write!(f, "problem at column {column} has no numbers")
+            }
+            Self::Arithmetic { column } => {
+                write!(f, "problem at column {column} overflows or divides by zero")
+            }
         }
     }
 }
@@ -102,8 +331,10 @@ impl MathProblem {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
-    const EXAMPLE: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
+    pub(super) const EXAMPLE: &str =
+        "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
 
     const INPUT: &str = include_str!("input.txt");
 
@@ -122,6 +353,85 @@ mod tests {
         assert_eq!(solution_part2(EXAMPLE), 3263827);
     }
 
+    #[test]
+    fn test_ragged_lines() {
+        // trailing spaces trimmed off every line, and a blank line at the end
+        let ragged = EXAMPLE
+            .lines()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n\n";
+        assert_eq!(solution_part1(&ragged), 4277556);
+        assert_eq!(solution_part2(&ragged), 3263827);
+
+        // operators don't have to line up with the first column of their problem
+        let shifted = " 12 3\n  4 56\n  *  +";
+        let operators = OperatorTable::default();
+        assert_eq!(solution_part1_with(shifted, &operators), Ok(48 + 59));
+        assert_eq!(solution_part2_with(shifted, &operators), Ok(24 + 6 + 35));
+
+        assert_eq!(
+            solution_part1_with("1  2\n3  4\n+", &operators),
+            Err(WorksheetError::MissingOperator { column: 3 })
+        );
+        assert_eq!(
+            solution_part1_with("1  x\n3  4\n+  *", &operators),
+            Err(WorksheetError::InvalidNumber {
+                number: "x".to_string(),
+                line: 0,
+                column: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_operators() {
+        let input = "10 7   20 3  \n4  100 6  9  \n2  3   5  7  \n-  %   /  min";
+        let operators = OperatorTable::default();
+        // 10 - 4 - 2 + 7 % 100 % 3 + 20 / 6 / 5 + min(3, 9, 7)
+        assert_eq!(solution_part1_with(input, &operators), Ok(8));
+
+        let operators = operators.with("pow", |a, b| a.checked_pow(b.try_into().ok()?));
+        assert_eq!(
+            solution_part1_with("2   3\n10  2\npow max", &operators),
+            Ok(1024 + 3)
+        );
+    }
+
+    #[test]
+    fn test_operator_errors() {
+        let operators = OperatorTable::default();
+        assert_eq!(
+            solution_part1_with("1 2 3\n4 5 6\n+ ^ *", &operators),
+            Err(WorksheetError::UnknownOperator {
+                operator: "^".to_string(),
+                column: 2
+            })
+        );
+        assert_eq!(
+            solution_part2_with("01 3\n0  4\n/  +", &operators),
+            Err(WorksheetError::Arithmetic { column: 0 })
+        );
+
+        // overflows a u64 but not an i128
+        let big = "4294967296\n4294967296\n4294967296\n*";
+        assert_eq!(solution_part1_with(big, &operators), Ok(1 << 96));
+        let huge = format!("{}*", "4294967296\n".repeat(5));
+        assert_eq!(
+            solution_part1_with(&huge, &operators),
+            Err(WorksheetError::Arithmetic { column: 0 })
+        );
+    }
+
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 4277556);
+            assert_eq!(solution_part2(&input), 3263827);
+        }
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(solution_part2(INPUT), 9077004354241);
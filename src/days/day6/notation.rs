@@ -0,0 +1,166 @@
+use super::{MathProblem, MathWorksheet, OperatorTable, WorksheetError};
+
+// How the numbers of a worksheet are written
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Notation {
+    // a number per line, as in part 1
+    Human,
+    // a number per column read top to bottom, columns right to left, as in part 2
+    Cephalopod,
+}
+
+impl Notation {
+    pub fn other(self) -> Self {
+        match self {
+            Self::Human => Self::Cephalopod,
+            Self::Cephalopod => Self::Human,
+        }
+    }
+}
+
+// Reads a worksheet written in `from` and writes the same problems in the other notation
+pub fn transcode(
+    input: &str,
+    from: Notation,
+    operators: &OperatorTable,
+) -> Result<String, WorksheetError> {
+    Ok(MathWorksheet::parse(input, from, operators)?.render(from.other()))
+}
+
+impl MathWorksheet {
+    // The worksheet written in `notation`: problems side by side with a blank column between
+    // them and their operator under their first column. Every line has the same length.
+    pub fn render(&self, notation: Notation) -> String {
+        let blocks = self
+            .problems
+            .iter()
+            .map(|problem| match notation {
+                Notation::Human => human_block(problem),
+                Notation::Cephalopod => cephalopod_block(problem),
+            })
+            .collect::<Vec<_>>();
+        let height = blocks.iter().map(|b| b.len()).max().unwrap_or(1);
+
+        let mut lines = vec![String::new(); height];
+        for (i, block) in blocks.iter().enumerate() {
+            let width = block.last().map_or(0, |l| l.len());
+            // numbers are pushed down to the operator line when the problem has fewer lines
+            let padding = height - block.len();
+            for (row, line) in lines.iter_mut().enumerate() {
+                if i > 0 {
+                    line.push(' ');
+                }
+                match row.checked_sub(padding) {
+                    Some(r) => line.push_str(&block[r]),
+                    None => line.push_str(&" ".repeat(width)),
+                }
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+// Lines of a problem, all the same width, the last one being its operator
+fn human_block(problem: &MathProblem) -> Vec<String> {
+    let width = problem
+        .numbers
+        .iter()
+        .map(|n| n.to_string().len())
+        .chain([problem.operator.len()])
+        .max()
+        .unwrap();
+
+    problem
+        .numbers
+        .iter()
+        .map(|n| format!("{n:>width$}"))
+        .chain([format!("{:<width$}", problem.operator)])
+        .collect()
+}
+
+fn cephalopod_block(problem: &MathProblem) -> Vec<String> {
+    // rightmost column first
+    let columns = problem
+        .numbers
+        .iter()
+        .rev()
+        .map(|n| n.to_string().chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let width = columns.len().max(problem.operator.len());
+    let height = columns.iter().map(|c| c.len()).max().unwrap_or(0);
+
+    let mut lines = (0..height)
+        .map(|row| {
+            let line = columns
+                .iter()
+                .map(|digits| {
+                    // digits pushed down to the bottom, like numbers aligned to the right
+                    (row + digits.len())
+                        .checked_sub(height)
+                        .map_or(' ', |r| digits[r])
+                })
+                .collect::<String>();
+            format!("{line:<width$}")
+        })
+        .collect::<Vec<_>>();
+    lines.push(format!("{:<width$}", problem.operator));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day6::tests::EXAMPLE;
+
+    #[test]
+    fn test_round_trip_example() {
+        let operators = OperatorTable::default();
+        for notation in [Notation::Human, Notation::Cephalopod] {
+            let worksheet = MathWorksheet::parse(EXAMPLE, notation, &operators).unwrap();
+            for to in [notation, notation.other()] {
+                let rendered = worksheet.render(to);
+                assert_eq!(
+                    MathWorksheet::parse(&rendered, to, &operators),
+                    Ok(worksheet.clone())
+                );
+            }
+        }
+
+        // the problems of part 2 written the way of part 1
+        let human = transcode(EXAMPLE, Notation::Cephalopod, &operators).unwrap();
+        assert_eq!(
+            human,
+            "356   8 175   4\n 24 248 581 431\n  1 369  32 623\n*   +   *   +  "
+        );
+        assert_eq!(crate::days::day6::solution_part1(&human), 3263827);
+    }
+
+    #[test]
+    fn test_round_trip_uneven() {
+        let operators = OperatorTable::default();
+        let worksheet = MathWorksheet::parse(
+            "1000 7   -5\n   2     30\n  10\n+    min -",
+            Notation::Human,
+            &operators,
+        )
+        .unwrap();
+
+        let rendered = worksheet.render(Notation::Human);
+        assert_eq!(
+            MathWorksheet::parse(&rendered, Notation::Human, &operators),
+            Ok(worksheet.clone())
+        );
+        let rendered = worksheet.render(Notation::Cephalopod);
+        assert_eq!(
+            MathWorksheet::parse(&rendered, Notation::Cephalopod, &operators),
+            Ok(worksheet.clone())
+        );
+        assert_eq!(
+            MathWorksheet::parse(&rendered, Notation::Cephalopod, &operators)
+                .unwrap()
+                .solve(),
+            worksheet.solve()
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::{cmp::Ordering, fmt::Display};
+
+// Arbitrary-precision unsigned integer, just enough of it to count timelines that don't fit in
+// any primitive and to keep exact probabilities. Little-endian base 2^32 limbs, with no trailing
+// zero limbs.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self { limbs: vec![] }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (&self.limbs, &other.limbs)
+        } else {
+            (&other.limbs, &self.limbs)
+        };
+
+        let mut limbs = Vec::with_capacity(long.len() + 1);
+        let mut carry = 0u64;
+        for (i, limb) in long.iter().enumerate() {
+            let sum = *limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        if carry != 0 {
+            limbs.push(carry as u32);
+        }
+
+        Self { limbs }
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let current = limbs[i + j] as u64 + *a as u64 * *b as u64 + carry;
+                limbs[i + j] = current as u32;
+                carry = current >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        let mut product = Self { limbs };
+        product.trim();
+        product
+    }
+
+    // Panics if other is bigger, there's no negative
+    pub fn sub(&self, other: &Self) -> Self {
+        assert!(*self >= *other, "subtraction underflow");
+
+        let mut difference = self.clone();
+        difference.sub_in_place(other);
+        difference
+    }
+
+    // Schoolbook binary long division, slow but plenty for a few hundred bits
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        assert!(!divisor.is_zero(), "division by zero");
+        if self < divisor {
+            return (Self::zero(), self.clone());
+        }
+
+        let mut quotient = vec![0u32; self.limbs.len()];
+        let mut remainder = Self::zero();
+        for bit in (0..self.limbs.len() * 32).rev() {
+            remainder.shift_in(self.limbs[bit / 32] >> (bit % 32) & 1);
+            if remainder >= *divisor {
+                remainder.sub_in_place(divisor);
+                quotient[bit / 32] |= 1 << (bit % 32);
+            }
+        }
+
+        let mut quotient = Self { limbs: quotient };
+        quotient.trim();
+        (quotient, remainder)
+    }
+
+    // self = self * 2 + bit
+    fn shift_in(&mut self, bit: u32) {
+        let mut carry = bit;
+        for limb in self.limbs.iter_mut() {
+            let next_carry = *limb >> 31;
+            *limb = (*limb << 1) | carry;
+            carry = next_carry;
+        }
+        if carry != 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    fn sub_in_place(&mut self, other: &Self) {
+        let mut borrow = false;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let (current, b1) = limb.overflowing_sub(*other.limbs.get(i).unwrap_or(&0));
+            let (current, b2) = current.overflowing_sub(borrow as u32);
+            *limb = current;
+            borrow = b1 || b2;
+        }
+        self.trim();
+    }
+
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let remainder = a.div_rem(&b).1;
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+
+    // Divides in place by a small number, returning the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let current = (remainder << 32) | *limb as u64;
+            *limb = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+        self.trim();
+        remainder as u32
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> Self {
+        let mut limbs = vec![];
+        while value != 0 {
+            limbs.push(value as u32);
+            value >>= 32;
+        }
+        Self { limbs }
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // chunks of 9 decimal digits, least significant first
+        let mut chunks = vec![];
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(1_000_000_000));
+        }
+
+        write!(f, "{}", chunks.last().unwrap())?;
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{chunk:09}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_display() {
+        let a = BigUint::from(u128::MAX);
+        let sum = a.add(&BigUint::from(1));
+
+        assert_eq!(sum.to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(BigUint::from(1_000_000_007).to_string(), "1000000007");
+    }
+
+    #[test]
+    fn test_mul_div_gcd() {
+        let a = BigUint::from(u128::MAX).mul(&BigUint::from(1_000_000_007));
+        let (quotient, remainder) = a.add(&BigUint::from(5)).div_rem(&BigUint::from(u128::MAX));
+
+        assert_eq!(quotient, BigUint::from(1_000_000_007));
+        assert_eq!(remainder, BigUint::from(5));
+        assert_eq!(a.sub(&a), BigUint::zero());
+        assert_eq!(
+            BigUint::from(2u128.pow(100) * 3).gcd(&BigUint::from(2u128.pow(70) * 9)),
+            BigUint::from(2u128.pow(70) * 3)
+        );
+    }
+}
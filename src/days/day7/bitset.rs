@@ -0,0 +1,140 @@
+// One row of the manifold as a bitset, a bit per column packed in machine words
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BitRow {
+    words: Vec<u64>,
+    width: usize,
+}
+
+impl BitRow {
+    pub fn new(width: usize) -> Self {
+        Self {
+            words: vec![0; width.div_ceil(64)],
+            width,
+        }
+    }
+
+    // A column per byte, set where `is_set` is true
+    pub fn from_bytes(bytes: &[u8], is_set: impl Fn(&u8) -> bool) -> Self {
+        Self {
+            words: bytes
+                .chunks(64)
+                .map(|chunk| {
+                    chunk
+                        .iter()
+                        .enumerate()
+                        .fold(0, |word, (i, b)| word | (is_set(b) as u64) << i)
+                })
+                .collect(),
+            width: bytes.len(),
+        }
+    }
+
+    pub fn set(&mut self, column: usize) {
+        self.words[column / 64] |= 1 << (column % 64);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn and(&self, other: &Self) -> Self {
+        self.zip(other, |a, b| a & b)
+    }
+
+    pub fn and_not(&self, other: &Self) -> Self {
+        self.zip(other, |a, b| a & !b)
+    }
+
+    pub fn or_assign(&mut self, other: &Self) {
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    // Every column moved by `offset`, dropping the ones that end up outside the row
+    pub fn shifted(&self, offset: isize) -> Self {
+        let len = self.words.len();
+        let (word_shift, bit_shift) = (offset.unsigned_abs() / 64, offset.unsigned_abs() % 64);
+        let mut words = vec![0; len];
+
+        if offset >= 0 {
+            for (i, word) in words.iter_mut().enumerate().skip(word_shift) {
+                let from = i - word_shift;
+                *word = self.words[from] << bit_shift;
+                if bit_shift > 0 && from > 0 {
+                    *word |= self.words[from - 1] >> (64 - bit_shift);
+                }
+            }
+        } else {
+            for (i, word) in words
+                .iter_mut()
+                .enumerate()
+                .take(len.saturating_sub(word_shift))
+            {
+                let from = i + word_shift;
+                *word = self.words[from] >> bit_shift;
+                if bit_shift > 0 && from + 1 < len {
+                    *word |= self.words[from + 1] << (64 - bit_shift);
+                }
+            }
+        }
+
+        if !self.width.is_multiple_of(64) {
+            words[len - 1] &= (1 << (self.width % 64)) - 1;
+        }
+
+        Self {
+            words,
+            width: self.width,
+        }
+    }
+
+    fn zip(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| op(*a, *b))
+                .collect(),
+            width: self.width,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shifted_across_words() {
+        let mut row = BitRow::new(130);
+        row.set(0);
+        row.set(63);
+        row.set(64);
+        row.set(129);
+
+        let mut expected = BitRow::new(130);
+        expected.set(1);
+        expected.set(64);
+        expected.set(65);
+        assert_eq!(row.shifted(1), expected);
+
+        let mut expected = BitRow::new(130);
+        expected.set(62);
+        expected.set(63);
+        expected.set(128);
+        assert_eq!(row.shifted(-1), expected);
+
+        let mut expected = BitRow::new(130);
+        expected.set(0);
+        expected.set(1);
+        expected.set(66);
+        assert_eq!(row.shifted(-63), expected);
+        assert_eq!(row.shifted(200).count_ones(), 0);
+    }
+}
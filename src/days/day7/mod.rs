@@ -1,59 +1,574 @@
 // https://adventofcode.com/2025/day/7
 
-use std::collections::BTreeSet;
+pub mod bigint;
+mod bitset;
+pub mod ratio;
+pub mod tiles;
+pub mod timelines;
+
+use std::{cmp::Reverse, fmt::Display};
+
+use bigint::BigUint;
+use bitset::BitRow;
+use ratio::Ratio;
+use tiles::{Tile, TileSet};
+
+use crate::input::normalize;
 
 pub fn solution_part1(input: &str) -> usize {
-    let input = input.trim().as_bytes();
-    let mut splits = 0;
-    let mut i = 0;
-    let row_size;
-    let mut beams: BTreeSet<usize> = BTreeSet::from_iter(vec![{
-        let s_column;
-        loop {
-            if input[i] == b'S' {
-                s_column = i;
-                while input[i] != b'\n' {
-                    i += 1;
-                }
-                row_size = i;
-                i += 1;
+    Manifold::from_input(input).count_splits()
+}
+
+pub fn solution_part2(input: &str) -> usize {
+    solution_part2_with::<usize>(input).unwrap()
+}
+
+// Timelines counted with any Counter, e.g. u128 or BigUint for manifolds too tall for usize.
+// With more than one source it's the timelines of all of them together.
+pub fn solution_part2_with<C: Counter>(input: &str) -> Result<C, Overflow> {
+    let manifold = Manifold::from_input(input);
+    let timelines = manifold.count_timelines::<C>();
+
+    // no sources, no timelines
+    manifold
+        .sources()
+        .iter()
+        .try_fold(C::zero(), |total, source| {
+            let count = timelines[source.0 * manifold.width + source.1].clone()?;
+            total.checked_add(&count).ok_or(Overflow {
+                row: source.0,
+                column: source.1,
+            })
+        })
+}
+
+// Tachyon manifold, padded to a rectangle. Any `S` is a source of a beam going down and every
+// byte behaves as its Tile says, by default `^` being a splitter sending the beam down on both
+// sides. Beams going past the left or right edge leave the manifold there and count as exits.
+pub struct Manifold {
+    cells: Vec<u8>,
+    width: usize,
+    height: usize,
+    sources: Vec<(usize, usize)>,
+    tiles: TileSet,
+    // where each tile that isn't a pass-through is, a bitset per row, for simulate
+    kinds: Vec<TileKind>,
+}
+
+struct TileKind {
+    tile: Tile,
+    rows: Vec<BitRow>,
+}
+
+// Everything about the beams of all sources going through the manifold at once.
+// Timelines are counted as in part 2: a beam hitting a splitter carries on in two timelines, and
+// one stopped by an absorber still counts as a timeline ending there.
+#[derive(PartialEq, Eq, Debug)]
+pub struct BeamReport<C> {
+    // splitters hit, i.e. the answer of part 1
+    pub splits: usize,
+    pub timelines: C,
+    // every tile that isn't a pass-through, top to bottom and left to right
+    pub splitters: Vec<SplitterStats<C>>,
+    pub unreached: Vec<(usize, usize)>,
+    // timelines leaving through each column of the bottom row
+    pub exits: Vec<C>,
+    // timelines leaving through the left and right edges
+    pub side_exits: (C, C),
+    pub absorbed: C,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct SplitterStats<C> {
+    pub position: (usize, usize),
+    pub hit: bool,
+    // timelines going through this splitter
+    pub timelines: C,
+}
+
+// Where a single beam ends up when splitters pick a side at random, see exit_distribution
+#[derive(PartialEq, Eq, Debug)]
+pub struct ExitDistribution {
+    // probability of leaving through each column of the bottom row
+    pub exits: Vec<Ratio>,
+    // probability of leaving through the left and right edges
+    pub side_exits: (Ratio, Ratio),
+    pub absorbed: Ratio,
+    pub expected_splits: Ratio,
+}
+
+// What happens to the beams of a single source, as in part 1 when it's the only one
+#[derive(PartialEq, Eq, Debug)]
+pub struct SourceReport<C> {
+    pub source: (usize, usize),
+    pub splits: usize,
+    // beams leaving the manifold through the left and right edges
+    pub side_exits: usize,
+    pub timelines: Result<C, Overflow>,
+}
+
+// Where a beam coming out of a tile ends up in the row below
+enum Output {
+    Column(usize),
+    Left,
+    Right,
+}
+
+impl Manifold {
+    pub fn from_input(input: &str) -> Self {
+        Self::with_tiles(input, TileSet::default())
+    }
+
+    pub fn with_tiles(input: &str, tiles: TileSet) -> Self {
+        let input = normalize(input);
+        let lines = input.trim().lines().collect::<Vec<_>>();
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        let height = lines.len();
+
+        let mut cells = vec![b'.'; width * height];
+        for (row, line) in lines.into_iter().enumerate() {
+            cells[row * width..][..line.len()].copy_from_slice(line.as_bytes());
+        }
+
+        let mut sources = vec![];
+        let mut present = [false; 256];
+        for (i, byte) in cells.iter().enumerate() {
+            present[*byte as usize] = true;
+            if *byte == b'S' {
+                sources.push((i / width, i % width));
+            }
+        }
+
+        // bytes in the manifold that are the same tile share a kind, pass-throughs don't get one
+        let mut kinds: Vec<(&Tile, [bool; 256])> = vec![];
+        for byte in (0..=255u8).filter(|b| present[*b as usize]) {
+            let tile = tiles.get(byte);
+            if tile.is_pass_through() {
+                continue;
+            }
+            match kinds.iter_mut().find(|(t, _)| *t == tile) {
+                Some((_, bytes)) => bytes[byte as usize] = true,
+                None => {
+                    let mut bytes = [false; 256];
+                    bytes[byte as usize] = true;
+                    kinds.push((tile, bytes));
+                }
+            }
+        }
+        let kinds = kinds
+            .into_iter()
+            .map(|(tile, bytes)| TileKind {
+                tile: tile.clone(),
+                rows: cells
+                    .chunks(width.max(1))
+                    .map(|row| BitRow::from_bytes(row, |b| bytes[*b as usize]))
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            cells,
+            width,
+            height,
+            sources,
+            tiles,
+            kinds,
+        }
+    }
+
+    // (row, column) of every source, top to bottom and left to right
+    pub fn sources(&self) -> &[(usize, usize)] {
+        &self.sources
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn tile(&self, row: usize, column: usize) -> &Tile {
+        self.tiles.get(self.cells[row * self.width + column])
+    }
+
+    // Splits with the beams of all sources going at once, beams in the same place are one beam
+    pub fn count_splits(&self) -> usize {
+        self.simulate(&self.sources).0
+    }
+
+    pub fn per_source<C: Counter>(&self) -> Vec<SourceReport<C>> {
+        let timelines = self.count_timelines::<C>();
+        self.sources
+            .iter()
+            .map(|source| {
+                let (splits, side_exits) = self.simulate(&[*source]);
+                SourceReport {
+                    source: *source,
+                    splits,
+                    side_exits,
+                    timelines: timelines[source.0 * self.width + source.1].clone(),
+                }
+            })
+            .collect()
+    }
+
+    // Walks the manifold top to bottom once, carrying how many timelines are in each column
+    pub fn analyze<C: Counter>(&self) -> Result<BeamReport<C>, Overflow> {
+        let add = |a: &C, b: &C, row, column| a.checked_add(b).ok_or(Overflow { row, column });
+
+        let mut splitters = vec![];
+        let mut side_exits = (C::zero(), C::zero());
+        let mut absorbed = C::zero();
+        let mut beams = vec![C::zero(); self.width];
+
+        for row in 0..self.height {
+            for (_, column) in self.sources.iter().filter(|(r, _)| *r == row) {
+                beams[*column] = add(&beams[*column], &C::one(), row, *column)?;
+            }
+
+            // the bottom row lets everything out, but its splitters still get hit as in part 1
+            if row + 1 == self.height {
+                for (column, timelines) in beams.iter().enumerate() {
+                    if !self.tile(row, column).is_pass_through() {
+                        splitters.push(SplitterStats {
+                            position: (row, column),
+                            hit: *timelines != C::zero(),
+                            timelines: timelines.clone(),
+                        });
+                    }
+                }
                 break;
             }
-            i += 1;
+
+            let mut next = vec![C::zero(); self.width];
+            for (column, timelines) in beams.into_iter().enumerate() {
+                let tile = self.tile(row, column);
+                let hit = timelines != C::zero();
+                if hit {
+                    if tile.outputs.is_empty() {
+                        absorbed = add(&absorbed, &timelines, row, column)?;
+                    }
+                    for offset in &tile.outputs {
+                        let exit = match self.output(column, *offset) {
+                            Output::Column(c) => &mut next[c],
+                            Output::Left => &mut side_exits.0,
+                            Output::Right => &mut side_exits.1,
+                        };
+                        *exit = add(exit, &timelines, row, column)?;
+                    }
+                }
+
+                if !tile.is_pass_through() {
+                    splitters.push(SplitterStats {
+                        position: (row, column),
+                        hit,
+                        timelines,
+                    });
+                }
+            }
+            beams = next;
+        }
+
+        let mut timelines = add(&side_exits.0, &side_exits.1, self.height, 0)?;
+        timelines = add(&timelines, &absorbed, self.height, 0)?;
+        for (column, exit) in beams.iter().enumerate() {
+            timelines = add(&timelines, exit, self.height, column)?;
+        }
+
+        Ok(BeamReport {
+            splits: splitters
+                .iter()
+                .filter(|s| s.hit && self.tile(s.position.0, s.position.1).splits())
+                .count(),
+            timelines,
+            unreached: splitters
+                .iter()
+                .filter(|s| !s.hit)
+                .map(|s| s.position)
+                .collect(),
+            splitters,
+            exits: beams,
+            side_exits,
+            absorbed,
+        })
+    }
+
+    // (splits, side exits) of the beams coming out of `sources`.
+    // Goes a row at a time with bitsets: the beams hitting each kind of tile are the beams masked
+    // with where that tile is, and they carry on shifted by each of the tile's outputs.
+    fn simulate(&self, sources: &[(usize, usize)]) -> (usize, usize) {
+        let mut splits = 0;
+        let mut side_exits = 0;
+        let mut beams = BitRow::new(self.width);
+
+        for row in 0..self.height {
+            for (_, column) in sources.iter().filter(|(r, _)| *r == row) {
+                beams.set(*column);
+            }
+
+            let mut next = beams.clone();
+            let mut outputs = vec![];
+            for kind in &self.kinds {
+                let hits = beams.and(&kind.rows[row]);
+                if hits.is_empty() {
+                    continue;
+                }
+
+                next = next.and_not(&hits);
+                let count = hits.count_ones();
+                if kind.tile.splits() {
+                    splits += count;
+                }
+                // the bottom row lets everything out downwards, as in count_timelines
+                if row + 1 == self.height {
+                    continue;
+                }
+                for offset in &kind.tile.outputs {
+                    let moved = hits.shifted(*offset);
+                    side_exits += count - moved.count_ones();
+                    outputs.push(moved);
+                }
+            }
+            for moved in &outputs {
+                next.or_assign(moved);
+            }
+            beams = next;
+        }
+
+        (splits, side_exits)
+    }
+
+    // Timelines of a beam starting from every cell, or where its count overflowed. A beam leaving
+    // through a side is one timeline, same as one reaching the bottom or getting absorbed.
+    pub fn count_timelines<C: Counter>(&self) -> Vec<Result<C, Overflow>> {
+        // a beam on the bottom row is a single timeline, whatever tile it's on
+        let bottom: fn(&Tile) -> Result<C, Overflow> = |_| Ok(C::one());
+        self.bottom_up(bottom, |row, column, _, outputs| {
+            if outputs.is_empty() {
+                return Ok(C::one());
+            }
+
+            // an overflow below is reported before this cell's own, picking the lowest one and
+            // the leftmost on ties, so it doesn't depend on the order the outputs are added in
+            let below = outputs
+                .iter()
+                .flatten()
+                .filter_map(|output| output.as_ref().err())
+                .min_by_key(|overflow| (Reverse(overflow.row), overflow.column));
+            if let Some(overflow) = below {
+                return Err(*overflow);
+            }
+
+            outputs
+                .into_iter()
+                .try_fold(C::zero(), |timelines, output| {
+                    let output = match output {
+                        Some(output) => output.clone()?,
+                        None => C::one(),
+                    };
+                    timelines
+                        .checked_add(&output)
+                        .ok_or(Overflow { row, column })
+                })
+        })
+    }
+
+    // Exact probabilities of where a single beam from `source` ends up when every two-way splitter
+    // sends it left with probability `left`. Tiles with any other number of outputs pick one of
+    // them uniformly.
+    pub fn exit_distribution(&self, source: (usize, usize), left: &Ratio) -> ExitDistribution {
+        check_probability(left);
+        let mut side_exits = (Ratio::zero(), Ratio::zero());
+        let mut absorbed = Ratio::zero();
+        let mut beams = vec![Ratio::zero(); self.width];
+        beams[source.1] = Ratio::one();
+
+        for row in source.0..self.height.saturating_sub(1) {
+            let mut next = vec![Ratio::zero(); self.width];
+            for (column, probability) in beams.into_iter().enumerate() {
+                if probability.is_zero() {
+                    continue;
+                }
+
+                let tile = self.tile(row, column);
+                if tile.outputs.is_empty() {
+                    absorbed = absorbed.add(&probability);
+                }
+                for (offset, weight) in tile.outputs.iter().zip(weights(tile, left)) {
+                    let exit = match self.output(column, *offset) {
+                        Output::Column(c) => &mut next[c],
+                        Output::Left => &mut side_exits.0,
+                        Output::Right => &mut side_exits.1,
+                    };
+                    *exit = exit.add(&probability.mul(&weight));
+                }
+            }
+            beams = next;
+        }
+
+        ExitDistribution {
+            exits: beams,
+            side_exits,
+            absorbed,
+            expected_splits: self.expected_splits(left)[source.0 * self.width + source.1].clone(),
         }
-        s_column
-    }]);
+    }
 
-    loop {
-        while i < input.len() && input[i] != b'\n' {
-            if input[i] == b'^' {
-                // row_size + 1 because we need to include \n
-                let column = i % (row_size + 1);
-                if let Some(_) = beams.take(&column) {
-                    splits += 1;
-                    beams.insert(column - 1);
-                    beams.insert(column + 1);
+    // Expected number of splitters a beam starting from every cell goes through, with the
+    // probabilities of exit_distribution
+    pub fn expected_splits(&self, left: &Ratio) -> Vec<Ratio> {
+        check_probability(left);
+        // a splitter on the bottom row is still hit, as in count_splits
+        let splits = |tile: &Tile| {
+            if tile.splits() {
+                Ratio::one()
+            } else {
+                Ratio::zero()
+            }
+        };
+        self.bottom_up(splits, |_, _, tile, outputs| {
+            let mut expected = splits(tile);
+            for (weight, output) in weights(tile, left).into_iter().zip(outputs) {
+                if let Some(output) = output {
+                    expected = expected.add(&weight.mul(output));
                 }
             }
-            i += 1;
+            expected
+        })
+    }
+
+    // Bottom-Up dynamic programming solution
+    // *_*
+    // Value of every cell, from `combine` of its tile and the values of the cells its outputs
+    // lead to in the row below (None for the ones leaving the manifold). The bottom row lets
+    // everything out, so its cells are only `bottom` of their tile.
+    fn bottom_up<T: Clone>(
+        &self,
+        bottom: impl Fn(&Tile) -> T,
+        combine: impl Fn(usize, usize, &Tile, Vec<Option<&T>>) -> T,
+    ) -> Vec<T> {
+        let mut dp = self
+            .cells
+            .iter()
+            .map(|byte| bottom(self.tiles.get(*byte)))
+            .collect::<Vec<_>>();
+
+        for row in (0..self.height.saturating_sub(1)).rev() {
+            let (current, below) = dp.split_at_mut((row + 1) * self.width);
+            let below = &below[..self.width];
+            for column in 0..self.width {
+                let tile = self.tile(row, column);
+                let outputs = tile
+                    .outputs
+                    .iter()
+                    .map(|offset| match self.output(column, *offset) {
+                        Output::Column(c) => Some(&below[c]),
+                        Output::Left | Output::Right => None,
+                    })
+                    .collect();
+                current[row * self.width + column] = combine(row, column, tile, outputs);
+            }
         }
 
-        if i == input.len() {
-            break;
-        } else {
-            i += 1;
+        dp
+    }
+
+    fn output(&self, column: usize, offset: isize) -> Output {
+        match column.checked_add_signed(offset) {
+            Some(c) if c < self.width => Output::Column(c),
+            Some(_) => Output::Right,
+            None => Output::Left,
         }
     }
+}
 
-    splits
+// Ratios can't be negative, but they can be more than 1
+fn check_probability(left: &Ratio) {
+    assert!(
+        left.numerator() <= left.denominator(),
+        "probability of going left should be between 0 and 1, not {left}"
+    );
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let input = input.trim().as_bytes();
-    let start = input.iter().position(|c| *c == b'S').unwrap();
-    let row_size = input.iter().position(|c| *c == b'\n').unwrap();
+// Probability of each output of a tile, `left` going to the leftmost of two wherever it's listed
+fn weights(tile: &Tile, left: &Ratio) -> Vec<Ratio> {
+    match tile.outputs.as_slice() {
+        [a, b] => {
+            let right = Ratio::one().sub(left);
+            if a <= b {
+                vec![left.clone(), right]
+            } else {
+                vec![right, left.clone()]
+            }
+        }
+        outputs => vec![Ratio::from_integers(1, outputs.len() as u128); outputs.len()],
+    }
+}
 
-    count_timelines(input, start, row_size)
+// Number type the timelines are counted with. The count doubles at every splitter, so adding
+// has to say when it doesn't fit anymore instead of wrapping around.
+pub trait Counter: Clone + PartialEq {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_counter {
+    ($($t:ty),*) => {
+        $(
+            impl Counter for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_counter!(u8, u16, u32, u64, u128, usize);
+
+impl Counter for BigUint {
+    fn zero() -> Self {
+        BigUint::zero()
+    }
+
+    fn one() -> Self {
+        BigUint::from(1)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self.add(other))
+    }
+}
+
+// The count of timelines going through this cell of the manifold didn't fit in the Counter.
+// Counting bottom up (count_timelines and part 2) it's the lowest overflowing cell under the one
+// being counted, leftmost on ties. Counting top down (analyze) it's the first one found, top to
+// bottom and left to right. Sources are added up last, an overflow there is at the source.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Overflow {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "timeline count overflows at row {}, column {}",
+            self.row, self.column
+        )
+    }
 }
 
 // Top-Down dynamic programming solution
@@ -84,28 +599,12 @@ fn through_timelines_recursive(
     cache[i]
 }
 
-// Bottom-Up dynamic programming solution
-// *_*
-fn count_timelines(input: &[u8], start: usize, row_size: usize) -> usize {
-    let mut dp = vec![1usize; input.len()];
-
-    for i in (0..input.len() - 1 - row_size - 1).rev() {
-        let down_i = i + row_size + 1;
-
-        match input[i] {
-            b'^' => dp[i] = dp[down_i - 1] + dp[down_i + 1],
-            _ => dp[i] = dp[down_i],
-        }
-    }
-
-    dp[start]
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
-    const EXAMPLE: &str = ".......S.......
+    pub(super) const EXAMPLE: &str = ".......S.......
 ...............
 .......^.......
 ...............
@@ -139,6 +638,263 @@ mod tests {
         assert_eq!(solution_part2(EXAMPLE), 40);
     }
 
+    #[test]
+    fn test_no_sources() {
+        assert_eq!(solution_part1("...\n.^."), 0);
+        assert_eq!(solution_part2("...\n.^."), 0);
+        assert_eq!(solution_part2_with::<BigUint>("..."), Ok(BigUint::zero()));
+    }
+
+    #[test]
+    fn test_part2_counters() {
+        assert_eq!(solution_part2_with::<u128>(EXAMPLE), Ok(40));
+        assert_eq!(
+            solution_part2_with::<BigUint>(EXAMPLE),
+            Ok(BigUint::from(40))
+        );
+
+        // every beam splits on each of the 9 rows of splitters
+        let small = pyramid(9);
+        assert_eq!(solution_part2_with::<u16>(&small), Ok(512));
+        // row 4 is the lowest where a count gets past 255, and column 8 its leftmost such cell
+        assert_eq!(
+            solution_part2_with::<u8>(&small),
+            Err(Overflow { row: 4, column: 8 })
+        );
+
+        let tall = pyramid(130);
+        assert!(solution_part2_with::<u128>(&tall).is_err());
+        assert_eq!(
+            solution_part2_with::<BigUint>(&tall).unwrap().to_string(),
+            "1361129467683753853853498429727072845824"
+        );
+    }
+
+    // Manifold where every beam hits a splitter on each of `rows` rows
+    fn pyramid(rows: usize) -> String {
+        let width = 2 * rows + 1;
+        let mut lines = vec![];
+        let mut line = vec![b'.'; width];
+        line[rows] = b'S';
+        lines.push(line);
+        for row in 0..rows {
+            lines.push(vec![b'.'; width]);
+            let mut line = vec![b'.'; width];
+            for column in (rows - row..=rows + row).step_by(2) {
+                line[column] = b'^';
+            }
+            lines.push(line);
+        }
+        lines.push(vec![b'.'; width]);
+
+        lines
+            .into_iter()
+            .map(|l| String::from_utf8(l).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_multiple_sources_and_edges() {
+        // the right source hits a splitter on the edge, losing one beam sideways
+        let manifold =
+            Manifold::from_input("..S....S\n........\n..^....^\n........\n.^.^..^.\n........");
+
+        assert_eq!(manifold.sources(), &[(0, 2), (0, 7)]);
+        assert_eq!(manifold.count_splits(), 5);
+        assert_eq!(
+            manifold.per_source::<usize>(),
+            vec![
+                SourceReport {
+                    source: (0, 2),
+                    splits: 3,
+                    side_exits: 0,
+                    timelines: Ok(4),
+                },
+                SourceReport {
+                    source: (0, 7),
+                    splits: 2,
+                    side_exits: 1,
+                    timelines: Ok(3),
+                },
+            ]
+        );
+        assert_eq!(
+            solution_part2_with::<usize>(
+                "..S....S\n........\n..^....^\n........\n.^.^..^.\n........"
+            ),
+            Ok(7)
+        );
+
+        // a splitter on the bottom row lets its beam out downwards, not through the sides
+        assert_eq!(
+            Manifold::from_input("S\n^").per_source::<usize>(),
+            vec![SourceReport {
+                source: (0, 0),
+                splits: 1,
+                side_exits: 0,
+                timelines: Ok(1),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_beam_report_example() {
+        let report = Manifold::from_input(EXAMPLE).analyze::<usize>().unwrap();
+
+        assert_eq!(report.splits, solution_part1(EXAMPLE));
+        assert_eq!(report.timelines, solution_part2(EXAMPLE));
+        assert_eq!(report.splitters.len(), 22);
+        // no beam makes it to column 9 of the last row of splitters
+        assert_eq!(report.unreached, vec![(14, 9)]);
+        assert_eq!(report.splitters[0].timelines, 1);
+        assert_eq!(report.side_exits, (0, 0));
+        assert_eq!(
+            report.exits,
+            vec![1, 0, 2, 0, 10, 0, 11, 0, 11, 0, 2, 1, 1, 0, 1]
+        );
+
+        // a splitter on the bottom row counts as a split, though its timeline ends there
+        let bottom = Manifold::from_input("S.\n^.");
+        let report = bottom.analyze::<usize>().unwrap();
+        assert_eq!(report.splits, bottom.count_splits());
+        assert_eq!(report.splits, 1);
+        assert_eq!(report.exits, vec![1, 0]);
+        assert!(report.unreached.is_empty());
+        let narrow = Manifold::from_input("S\n^");
+        assert_eq!(narrow.analyze::<usize>().unwrap().splits, 1);
+    }
+
+    #[test]
+    fn test_custom_tiles() {
+        // deflected left, then split three ways with one of them absorbed
+        let manifold =
+            Manifold::with_tiles("..S..\n..<..\n.*...\n..#.>\n.....", TileSet::extended());
+        let report = manifold.analyze::<usize>().unwrap();
+
+        assert_eq!(manifold.count_splits(), 1);
+        assert_eq!(report.splits, 1);
+        assert_eq!(report.timelines, 3);
+        assert_eq!(report.absorbed, 1);
+        assert_eq!(report.side_exits, (0, 0));
+        assert_eq!(report.exits, vec![1, 1, 0, 0, 0]);
+        assert_eq!(report.unreached, vec![(3, 4)]);
+        assert_eq!(manifold.per_source::<usize>()[0].timelines, Ok(3));
+
+        let manifold = Manifold::with_tiles(
+            "S\n.",
+            TileSet::default().with(b'S', Tile::right_deflector()),
+        );
+        assert_eq!(manifold.analyze::<usize>().unwrap().side_exits, (0, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "probability of going left should be between 0 and 1, not 3/2")]
+    fn test_exit_distribution_not_a_probability() {
+        Manifold::from_input(EXAMPLE).exit_distribution((0, 7), &Ratio::from_integers(3, 2));
+    }
+
+    #[test]
+    fn test_exit_distribution_example() {
+        let manifold = Manifold::from_input(EXAMPLE);
+
+        // a fair coin follows the timelines: each one has probability 1/2^splits
+        let fair = manifold.exit_distribution((0, 7), &Ratio::from_integers(1, 2));
+        assert_eq!(fair.exits[0], Ratio::from_integers(1, 2u128.pow(7)));
+        assert_eq!(
+            fair.exits
+                .iter()
+                .fold(Ratio::zero(), |total, exit| total.add(exit)),
+            Ratio::one()
+        );
+        assert_eq!(fair.expected_splits.to_string(), "295/64");
+
+        // always going left is a single timeline, out of column 1 after 7 splits
+        let left = manifold.exit_distribution((0, 7), &Ratio::one());
+        assert_eq!(left.exits[0], Ratio::one());
+        assert_eq!(left.expected_splits, Ratio::from_integers(7, 1));
+
+        // a splitter on the bottom row is hit even though the beam leaves there
+        let bottom = Manifold::from_input("S\n^");
+        assert_eq!(
+            bottom
+                .exit_distribution((0, 0), &Ratio::one())
+                .expected_splits,
+            Ratio::from_integers(bottom.count_splits() as u128, 1)
+        );
+
+        // left is left however a tile lists its outputs
+        let reversed =
+            Manifold::with_tiles(EXAMPLE, TileSet::empty().with(b'^', Tile::new(&[1, -1])));
+        assert_eq!(
+            reversed.exit_distribution((0, 7), &Ratio::one()),
+            manifold.exit_distribution((0, 7), &Ratio::one())
+        );
+    }
+
+    #[test]
+    fn test_splits_wide_manifold() {
+        // pseudo-random manifold of every tile kind, a few words wide
+        let mut seed = 7u64;
+        let mut lines = vec![format!(
+            "{}S{}S{}",
+            ".".repeat(40),
+            ".".repeat(90),
+            ".".repeat(69)
+        )];
+        for _ in 0..60 {
+            let line = (0..202)
+                .map(|_| {
+                    seed = seed
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    match seed >> 59 {
+                        0..=5 => '^',
+                        6 => '<',
+                        7 => '>',
+                        8 => '*',
+                        9 => '#',
+                        _ => '.',
+                    }
+                })
+                .collect::<String>();
+            lines.push(line);
+        }
+        lines.push(".".repeat(202));
+        let manifold = Manifold::with_tiles(&lines.join("\n"), TileSet::extended());
+
+        let report = manifold.analyze::<u128>().unwrap();
+        assert_eq!(manifold.count_splits(), report.splits);
+        for source in manifold.per_source::<u128>() {
+            let alone = Manifold::with_tiles(
+                &lines
+                    .iter()
+                    .enumerate()
+                    .map(|(row, line)| {
+                        line.char_indices()
+                            .map(|(column, c)| match c {
+                                'S' if (row, column) != source.source => '.',
+                                c => c,
+                            })
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                TileSet::extended(),
+            );
+            let report = alone.analyze::<u128>().unwrap();
+            assert_eq!(source.splits, report.splits);
+        }
+    }
+
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 21);
+            assert_eq!(solution_part2(&input), 40);
+        }
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(solution_part2(INPUT), 5921061943075);
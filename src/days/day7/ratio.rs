@@ -0,0 +1,96 @@
+use std::fmt::Display;
+
+use super::bigint::BigUint;
+
+// Non-negative fraction kept in lowest terms, for exact probabilities
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Ratio {
+    numerator: BigUint,
+    denominator: BigUint,
+}
+
+impl Ratio {
+    pub fn new(numerator: BigUint, denominator: BigUint) -> Self {
+        assert!(!denominator.is_zero(), "zero denominator");
+
+        let gcd = numerator.gcd(&denominator);
+        if gcd.is_zero() || gcd == BigUint::from(1) {
+            return Self {
+                numerator,
+                denominator,
+            };
+        }
+
+        Self {
+            numerator: numerator.div_rem(&gcd).0,
+            denominator: denominator.div_rem(&gcd).0,
+        }
+    }
+
+    pub fn from_integers(numerator: u128, denominator: u128) -> Self {
+        Self::new(BigUint::from(numerator), BigUint::from(denominator))
+    }
+
+    pub fn zero() -> Self {
+        Self::from_integers(0, 1)
+    }
+
+    pub fn one() -> Self {
+        Self::from_integers(1, 1)
+    }
+
+    pub fn numerator(&self) -> &BigUint {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &BigUint {
+        &self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        if self.denominator == other.denominator {
+            return Self::new(
+                self.numerator.add(&other.numerator),
+                self.denominator.clone(),
+            );
+        }
+
+        Self::new(
+            self.numerator
+                .mul(&other.denominator)
+                .add(&other.numerator.mul(&self.denominator)),
+            self.denominator.mul(&other.denominator),
+        )
+    }
+
+    // Panics if other is bigger
+    pub fn sub(&self, other: &Self) -> Self {
+        Self::new(
+            self.numerator
+                .mul(&other.denominator)
+                .sub(&other.numerator.mul(&self.denominator)),
+            self.denominator.mul(&other.denominator),
+        )
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self::new(
+            self.numerator.mul(&other.numerator),
+            self.denominator.mul(&other.denominator),
+        )
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denominator == BigUint::from(1) {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// What a tile does to a beam reaching it: the columns, relative to the tile, where the beam
+// carries on in the row below. A pass-through is [0], the puzzle's splitter [-1, 1] and an
+// absorber has no outputs at all.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Tile {
+    pub outputs: Vec<isize>,
+}
+
+impl Tile {
+    pub fn new(outputs: &[isize]) -> Self {
+        Self {
+            outputs: outputs.to_vec(),
+        }
+    }
+
+    pub fn pass_through() -> Self {
+        Self::new(&[0])
+    }
+
+    pub fn splitter() -> Self {
+        Self::new(&[-1, 1])
+    }
+
+    pub fn three_way_splitter() -> Self {
+        Self::new(&[-1, 0, 1])
+    }
+
+    pub fn left_deflector() -> Self {
+        Self::new(&[-1])
+    }
+
+    pub fn right_deflector() -> Self {
+        Self::new(&[1])
+    }
+
+    pub fn absorber() -> Self {
+        Self::new(&[])
+    }
+
+    // a beam reaching it goes more than one way
+    pub fn splits(&self) -> bool {
+        self.outputs.len() > 1
+    }
+
+    pub fn is_pass_through(&self) -> bool {
+        self.outputs == [0]
+    }
+}
+
+// Tile of every byte of the manifold, anything not set is a pass-through
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TileSet {
+    tiles: Vec<Tile>,
+}
+
+impl TileSet {
+    pub fn empty() -> Self {
+        Self {
+            tiles: vec![Tile::pass_through(); 256],
+        }
+    }
+
+    // `<` and `>` deflectors, `#` absorbers and `*` three-way splitters on top of the default
+    pub fn extended() -> Self {
+        Self::default()
+            .with(b'<', Tile::left_deflector())
+            .with(b'>', Tile::right_deflector())
+            .with(b'#', Tile::absorber())
+            .with(b'*', Tile::three_way_splitter())
+    }
+
+    pub fn with(mut self, byte: u8, tile: Tile) -> Self {
+        self.tiles[byte as usize] = tile;
+        self
+    }
+
+    pub fn get(&self, byte: u8) -> &Tile {
+        &self.tiles[byte as usize]
+    }
+}
+
+// The puzzle's manifold, where `^` is the only thing that isn't empty space
+impl Default for TileSet {
+    fn default() -> Self {
+        Self::empty().with(b'^', Tile::splitter())
+    }
+}
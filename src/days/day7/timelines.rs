@@ -0,0 +1,264 @@
+use super::{Manifold, Output, Overflow};
+
+// A single timeline of part 2: the way taken at every tile sending the beam more than one way,
+// and where the beam ends up.
+// Timelines are ordered lexicographically by their choices, leftmost output first.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Timeline {
+    pub source: (usize, usize),
+    pub choices: Vec<Choice>,
+    pub end: End,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Choice {
+    pub position: (usize, usize),
+    // output of the tile the beam went through, -1 left and 1 right for the puzzle's splitter
+    pub offset: isize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum End {
+    // column of the bottom row
+    Bottom(usize),
+    Left,
+    Right,
+    Absorbed((usize, usize)),
+}
+
+// Cell a beam is in, or how it left the manifold
+type Beam = Result<(usize, usize), End>;
+
+// Where a beam goes from a cell until the next choice
+enum Step {
+    Choose(Vec<isize>),
+    End(End),
+}
+
+impl Manifold {
+    // Every timeline of a beam from `source` in lexicographic order, found as they are asked
+    // for, so `.take(limit)` only walks that many of them
+    pub fn timelines(&self, source: (usize, usize)) -> Timelines<'_> {
+        Timelines {
+            manifold: self,
+            source,
+            pending: vec![(Ok(source), vec![])],
+        }
+    }
+
+    // The `k`-th timeline of a beam from `source` in lexicographic order (from 0), None if there
+    // aren't that many. Fails when the number of timelines doesn't fit a u128.
+    pub fn kth_timeline(
+        &self,
+        source: (usize, usize),
+        mut k: u128,
+    ) -> Result<Option<Timeline>, Overflow> {
+        let counts = self.count_timelines::<u128>();
+        if k >= counts[source.0 * self.width + source.1]? {
+            return Ok(None);
+        }
+
+        let mut position = source;
+        let mut choices = vec![];
+        loop {
+            let offsets = match self.follow(&mut position) {
+                Step::Choose(offsets) => offsets,
+                Step::End(end) => {
+                    return Ok(Some(Timeline {
+                        source,
+                        choices,
+                        end,
+                    }));
+                }
+            };
+
+            for offset in offsets {
+                // every timeline under the source is counted, so none of these overflow
+                let next = self.below(position, offset);
+                let count = match next {
+                    Ok((row, column)) => counts[row * self.width + column]?,
+                    Err(_) => 1,
+                };
+                if k >= count {
+                    k -= count;
+                    continue;
+                }
+
+                choices.push(Choice { position, offset });
+                match next {
+                    Ok(next) => position = next,
+                    Err(end) => {
+                        return Ok(Some(Timeline {
+                            source,
+                            choices,
+                            end,
+                        }));
+                    }
+                }
+                break;
+            }
+        }
+    }
+
+    // A timeline of a beam from `source` picked uniformly at random among all of them.
+    // `random(n)` has to give a uniformly random number in 0..n.
+    pub fn sample_timeline(
+        &self,
+        source: (usize, usize),
+        random: &mut impl FnMut(u128) -> u128,
+    ) -> Result<Timeline, Overflow> {
+        let total = self.count_timelines::<u128>()[source.0 * self.width + source.1]?;
+        Ok(self
+            .kth_timeline(source, random(total))?
+            .expect("random number out of range"))
+    }
+
+    // Moves `position` down through tiles with a single output until the beam has to pick a way
+    // (outputs sorted leftmost first) or its timeline ends
+    fn follow(&self, position: &mut (usize, usize)) -> Step {
+        loop {
+            let (row, column) = *position;
+            if row + 1 >= self.height {
+                return Step::End(End::Bottom(column));
+            }
+
+            let tile = self.tile(row, column);
+            match tile.outputs.as_slice() {
+                [] => return Step::End(End::Absorbed(*position)),
+                [offset] => match self.below(*position, *offset) {
+                    Ok(next) => *position = next,
+                    Err(end) => return Step::End(end),
+                },
+                outputs => {
+                    let mut offsets = outputs.to_vec();
+                    offsets.sort_unstable();
+                    return Step::Choose(offsets);
+                }
+            }
+        }
+    }
+
+    // Cell a beam gets to through `offset` of the tile at `position`, or how it leaves the sides
+    fn below(&self, (row, column): (usize, usize), offset: isize) -> Beam {
+        match self.output(column, offset) {
+            Output::Column(c) => Ok((row + 1, c)),
+            Output::Left => Err(End::Left),
+            Output::Right => Err(End::Right),
+        }
+    }
+}
+
+// Depth-first walk over the timelines, see Manifold::timelines
+pub struct Timelines<'a> {
+    manifold: &'a Manifold,
+    source: (usize, usize),
+    // where the beam is (or how it already left) and the choices that led there, with the next
+    // one to explore on top
+    pending: Vec<(Beam, Vec<Choice>)>,
+}
+
+impl Iterator for Timelines<'_> {
+    type Item = Timeline;
+
+    fn next(&mut self) -> Option<Timeline> {
+        loop {
+            let (beam, choices) = self.pending.pop()?;
+            let end = match beam {
+                Err(end) => end,
+                Ok(mut position) => match self.manifold.follow(&mut position) {
+                    Step::End(end) => end,
+                    Step::Choose(offsets) => {
+                        for offset in offsets.into_iter().rev() {
+                            let mut choices = choices.clone();
+                            choices.push(Choice { position, offset });
+                            self.pending
+                                .push((self.manifold.below(position, offset), choices));
+                        }
+                        continue;
+                    }
+                },
+            };
+
+            return Some(Timeline {
+                source: self.source,
+                choices,
+                end,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day7::{tests::EXAMPLE, tiles::TileSet};
+
+    #[test]
+    fn test_timelines_example() {
+        let manifold = Manifold::from_input(EXAMPLE);
+        let source = manifold.sources()[0];
+        let timelines = manifold.timelines(source).collect::<Vec<_>>();
+        assert_eq!(timelines.len(), 40);
+
+        // always going left first
+        let first = &timelines[0];
+        assert!(first.choices.iter().all(|c| c.offset == -1));
+        assert_eq!(first.end, End::Bottom(0));
+        assert_eq!(
+            manifold.timelines(source).take(3).collect::<Vec<_>>(),
+            timelines[..3]
+        );
+
+        let offsets = |t: &Timeline| t.choices.iter().map(|c| c.offset).collect::<Vec<_>>();
+        assert!(
+            timelines
+                .windows(2)
+                .all(|w| offsets(&w[0]) < offsets(&w[1]))
+        );
+        for (k, timeline) in timelines.iter().enumerate() {
+            assert_eq!(
+                manifold.kth_timeline(source, k as u128),
+                Ok(Some(timeline.clone()))
+            );
+        }
+        assert_eq!(manifold.kth_timeline(source, 40), Ok(None));
+    }
+
+    #[test]
+    fn test_sample_timeline() {
+        let manifold = Manifold::from_input(EXAMPLE);
+        let source = manifold.sources()[0];
+        let timelines = manifold.timelines(source).collect::<Vec<_>>();
+
+        let mut seed = 1u128;
+        let mut random = |n: u128| {
+            seed = seed.wrapping_mul(25214903917).wrapping_add(11) % (1 << 48);
+            (seed >> 16) % n
+        };
+        for _ in 0..20 {
+            let timeline = manifold.sample_timeline(source, &mut random).unwrap();
+            assert!(timelines.contains(&timeline));
+        }
+        assert_eq!(
+            manifold.sample_timeline(source, &mut |n| n - 1),
+            Ok(timelines[39].clone())
+        );
+    }
+
+    #[test]
+    fn test_timelines_ends() {
+        let manifold = Manifold::with_tiles("S..\n^..\n.*.\n#.>\n...", TileSet::extended());
+        let ends = manifold
+            .timelines((0, 0))
+            .map(|t| t.end)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ends,
+            vec![End::Left, End::Absorbed((3, 0)), End::Bottom(1), End::Right]
+        );
+        assert_eq!(
+            manifold.kth_timeline((0, 0), 1).unwrap().unwrap().end,
+            End::Absorbed((3, 0))
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use std::cmp::Ordering;
+
+use super::{
+    Connection, DisjointSet, Metric, Pos, TieBreak, closest_connections, connection_number,
+};
+
+// Single-linkage clustering of the boxes: the connections of the minimum spanning tree in the
+// order they are made, each one merging two circuits into a bigger one.
+// Circuits are numbered like a linkage matrix: 0..n are the boxes on their own and n + m is the
+// circuit created by the m-th merge.
+pub struct Dendrogram<'a, const D: usize = 3> {
+    boxes: &'a [Pos<D>],
+    metric: Metric,
+    tie_break: TieBreak,
+    merges: Vec<Merge>,
+    // circuit each circuit got merged into, None for the final one
+    parents: Vec<Option<usize>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Merge {
+    pub connection: Connection,
+    pub circuits: (usize, usize),
+    pub size: usize,
+}
+
+impl<'a, const D: usize> Dendrogram<'a, D> {
+    pub fn new(boxes: &'a [Pos<D>], metric: Metric, tie_break: TieBreak) -> Self {
+        let n = boxes.len();
+        let mut merges = Vec::with_capacity(n.saturating_sub(1));
+        let mut parents = vec![None; (2 * n).saturating_sub(1)];
+        let mut sizes = vec![1; n];
+        // circuit currently containing each box, through the DisjointSet's roots
+        let mut circuits = DisjointSet::new(n);
+        let mut circuit_of_root = (0..n).collect::<Vec<_>>();
+
+        for connection in super::minimum_spanning_tree(boxes, metric, tie_break) {
+            let (i, j) = connection.boxes;
+            let (root_i, root_j) = (circuits.find(i), circuits.find(j));
+            let (circuit_i, circuit_j) = (circuit_of_root[root_i], circuit_of_root[root_j]);
+            let circuit = n + merges.len();
+            let size = sizes[circuit_i] + sizes[circuit_j];
+
+            parents[circuit_i] = Some(circuit);
+            parents[circuit_j] = Some(circuit);
+            sizes.push(size);
+            circuits.union(i, j);
+            circuit_of_root[circuits.find(i)] = circuit;
+            merges.push(Merge {
+                connection,
+                circuits: (circuit_i, circuit_j),
+                size,
+            });
+        }
+
+        Self {
+            boxes,
+            metric,
+            tie_break,
+            merges,
+            parents,
+        }
+    }
+
+    // Merges in the order they happen, i.e. the minimum spanning tree with its distances
+    pub fn merges(&self) -> &[Merge] {
+        &self.merges
+    }
+
+    // How many connections (counting the ones between boxes already in the same circuit) are
+    // made up to and including this one
+    pub fn connection_number(&self, connection: &Connection) -> usize {
+        connection_number(self.boxes, connection, self.metric, self.tie_break)
+    }
+
+    // Circuits after connecting the `connections` closest pairs, largest first.
+    // All those pairs end up in the same circuit whether or not they're in the spanning tree, so
+    // it's enough to find the last pair and apply every merge up to it.
+    pub fn circuits_after(&self, connections: usize) -> Vec<Vec<usize>> {
+        self.circuits_after_merges(self.merges_after(connections))
+    }
+
+    // Boxes in the same circuit as `box_index` after `connections` connections
+    pub fn circuit_of(&self, box_index: usize, connections: usize) -> Vec<usize> {
+        let merges = self.merges_after(connections);
+        let mut circuit = box_index;
+        while let Some(parent) = self.parents[circuit] {
+            if parent - self.boxes.len() >= merges {
+                break;
+            }
+            circuit = parent;
+        }
+
+        let mut members = self.members(circuit);
+        members.sort_unstable();
+        members
+    }
+
+    // Connections needed before there are at most `circuits` circuits, None for 0 circuits
+    pub fn connections_to_reach(&self, circuits: usize) -> Option<usize> {
+        if circuits == 0 {
+            return None;
+        }
+        if circuits >= self.boxes.len() {
+            return Some(0);
+        }
+
+        let merge = &self.merges[self.boxes.len() - circuits - 1];
+        Some(self.connection_number(&merge.connection))
+    }
+
+    // The merge that first put both boxes in the same circuit, None if they're the same box
+    pub fn merged_by(&self, a: usize, b: usize) -> Option<&Merge> {
+        if a == b {
+            return None;
+        }
+
+        let mut ancestors_of_a = vec![false; self.parents.len()];
+        let mut circuit = Some(a);
+        while let Some(c) = circuit {
+            ancestors_of_a[c] = true;
+            circuit = self.parents[c];
+        }
+
+        let mut circuit = self.parents[b];
+        while let Some(c) = circuit {
+            if ancestors_of_a[c] {
+                return Some(&self.merges[c - self.boxes.len()]);
+            }
+            circuit = self.parents[c];
+        }
+
+        None
+    }
+
+    // Circuits when every pair within `threshold` is connected, largest first.
+    // The threshold is in the units of the metric, so squared for Euclidean.
+    pub fn circuits_within(&self, threshold: i128) -> Vec<Vec<usize>> {
+        let merges = self
+            .merges
+            .partition_point(|m| m.connection.distance <= threshold);
+        self.circuits_after_merges(merges)
+    }
+
+    // Smallest threshold leaving at most `circuits` circuits, None for 0 circuits
+    pub fn threshold_for(&self, circuits: usize) -> Option<i128> {
+        if circuits == 0 {
+            return None;
+        }
+        if circuits >= self.boxes.len() {
+            return Some(0);
+        }
+
+        Some(
+            self.merges[self.boxes.len() - circuits - 1]
+                .connection
+                .distance,
+        )
+    }
+
+    fn merges_after(&self, connections: usize) -> usize {
+        match closest_connections(self.boxes, connections, self.metric, self.tie_break).last() {
+            Some(last) => self
+                .merges
+                .partition_point(|m| self.tie_break.cmp(&m.connection, last) != Ordering::Greater),
+            None => 0,
+        }
+    }
+
+    fn circuits_after_merges(&self, merges: usize) -> Vec<Vec<usize>> {
+        let mut circuits = DisjointSet::new(self.boxes.len());
+        for merge in &self.merges[..merges] {
+            let (i, j) = merge.connection.boxes;
+            circuits.union(i, j);
+        }
+
+        let mut members = vec![vec![]; self.boxes.len()];
+        for i in 0..self.boxes.len() {
+            members[circuits.find(i)].push(i);
+        }
+        let mut members = members
+            .into_iter()
+            .filter(|m| !m.is_empty())
+            .collect::<Vec<_>>();
+        members.sort_by_key(|m| std::cmp::Reverse(m.len()));
+        members
+    }
+
+    fn members(&self, circuit: usize) -> Vec<usize> {
+        let mut members = vec![];
+        let mut to_visit = vec![circuit];
+        while let Some(c) = to_visit.pop() {
+            if c < self.boxes.len() {
+                members.push(c);
+            } else {
+                let (left, right) = self.merges[c - self.boxes.len()].circuits;
+                to_visit.push(left);
+                to_visit.push(right);
+            }
+        }
+        members
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day8::tests::EXAMPLE;
+
+    #[test]
+    fn test_circuits_after_example() {
+        let boxes = Pos::<3>::from_input(EXAMPLE);
+        let dendrogram = Dendrogram::new(&boxes, Metric::Euclidean, TieBreak::Lexicographic);
+
+        let sizes = dendrogram
+            .circuits_after(10)
+            .iter()
+            .map(|c| c.len())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![5, 4, 2, 2, 1, 1, 1, 1, 1, 1, 1]);
+
+        let largest = &dendrogram.circuits_after(10)[0];
+        assert_eq!(&dendrogram.circuit_of(largest[0], 10), largest);
+    }
+
+    #[test]
+    fn test_threshold_example() {
+        let boxes = Pos::<3>::from_input(EXAMPLE);
+        let dendrogram = Dendrogram::new(&boxes, Metric::Euclidean, TieBreak::Lexicographic);
+
+        let tenth = closest_connections(&boxes, 10, Metric::Euclidean, TieBreak::Lexicographic)
+            .pop()
+            .unwrap();
+        assert_eq!(
+            dendrogram.circuits_within(tenth.distance),
+            dendrogram.circuits_after(10)
+        );
+
+        for circuits in [1, 3, 11] {
+            let threshold = dendrogram.threshold_for(circuits).unwrap();
+            assert!(dendrogram.circuits_within(threshold).len() <= circuits);
+            assert!(dendrogram.circuits_within(threshold - 1).len() > circuits);
+        }
+        assert_eq!(dendrogram.threshold_for(0), None);
+    }
+
+    #[test]
+    fn test_merges_example() {
+        let boxes = Pos::<3>::from_input(EXAMPLE);
+        let dendrogram = Dendrogram::new(&boxes, Metric::Euclidean, TieBreak::Lexicographic);
+
+        let last = dendrogram.merges().last().unwrap();
+        assert_eq!(last.size, boxes.len());
+        assert_eq!(last.connection.boxes, (10, 12));
+
+        let (i, j) = last.connection.boxes;
+        assert_eq!(dendrogram.merged_by(i, j), Some(last));
+        assert_eq!(dendrogram.merged_by(i, i), None);
+
+        // 162,817,812 and 425,690,689 are the first connection
+        assert_eq!(
+            dendrogram.merged_by(0, 19).unwrap().connection.boxes,
+            (0, 19)
+        );
+        assert_eq!(dendrogram.connections_to_reach(boxes.len()), Some(0));
+        assert_eq!(dendrogram.connections_to_reach(boxes.len() - 1), Some(1));
+        assert_eq!(
+            dendrogram.connections_to_reach(1),
+            Some(dendrogram.connection_number(&last.connection))
+        );
+    }
+}
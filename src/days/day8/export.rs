@@ -0,0 +1,180 @@
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+};
+
+use super::{DisjointSet, Metric, Pos, TieBreak, closest_connections};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SceneFormat {
+    Ply,
+    // vertex colours use the common `v x y z r g b` extension
+    Obj,
+}
+
+// Colours for the largest circuits, biggest first; the rest cycle through them again
+const PALETTE: [[u8; 3]; 10] = [
+    [230, 25, 75],
+    [60, 180, 75],
+    [0, 130, 200],
+    [245, 130, 48],
+    [145, 30, 180],
+    [70, 240, 240],
+    [240, 50, 230],
+    [210, 245, 60],
+    [255, 225, 25],
+    [170, 110, 40],
+];
+
+// Boxes on their own, not connected to anything
+const LONELY: [u8; 3] = [128, 128, 128];
+
+// Writes the boxes as vertices coloured by circuit and the `connections` closest pairs as edges
+pub fn export_scene<W: Write>(
+    out: &mut W,
+    boxes: &[Pos<3>],
+    connections: usize,
+    metric: Metric,
+    tie_break: TieBreak,
+    format: SceneFormat,
+) -> io::Result<()> {
+    let edges = closest_connections(boxes, connections, metric, tie_break);
+    let colours = circuit_colours(boxes.len(), edges.iter().map(|c| c.boxes));
+
+    match format {
+        SceneFormat::Ply => {
+            writeln!(out, "ply")?;
+            writeln!(out, "format ascii 1.0")?;
+            writeln!(
+                out,
+                "comment junction boxes after {} connections",
+                edges.len()
+            )?;
+            writeln!(out, "element vertex {}", boxes.len())?;
+            for axis in ["x", "y", "z"] {
+                writeln!(out, "property double {axis}")?;
+            }
+            for channel in ["red", "green", "blue"] {
+                writeln!(out, "property uchar {channel}")?;
+            }
+            writeln!(out, "element edge {}", edges.len())?;
+            writeln!(out, "property int vertex1")?;
+            writeln!(out, "property int vertex2")?;
+            writeln!(out, "end_header")?;
+
+            for (pos, [r, g, b]) in boxes.iter().zip(&colours) {
+                let [x, y, z] = pos.coordinates;
+                writeln!(out, "{x} {y} {z} {r} {g} {b}")?;
+            }
+            for edge in &edges {
+                writeln!(out, "{} {}", edge.boxes.0, edge.boxes.1)?;
+            }
+        }
+        SceneFormat::Obj => {
+            writeln!(out, "# junction boxes after {} connections", edges.len())?;
+            for (pos, colour) in boxes.iter().zip(&colours) {
+                let [x, y, z] = pos.coordinates;
+                let [r, g, b] = colour.map(|c| c as f64 / 255.0);
+                writeln!(out, "v {x} {y} {z} {r:.3} {g:.3} {b:.3}")?;
+            }
+            // OBJ indexes start at 1
+            for edge in &edges {
+                writeln!(out, "l {} {}", edge.boxes.0 + 1, edge.boxes.1 + 1)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn save_scene(
+    path: &str,
+    input: &str,
+    connections: usize,
+    format: SceneFormat,
+) -> io::Result<()> {
+    let boxes = Pos::<3>::from_input(input);
+    let mut f = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    export_scene(
+        &mut f,
+        &boxes,
+        connections,
+        Metric::Euclidean,
+        TieBreak::Lexicographic,
+        format,
+    )
+}
+
+fn circuit_colours(len: usize, edges: impl Iterator<Item = (usize, usize)>) -> Vec<[u8; 3]> {
+    let mut circuits = DisjointSet::new(len);
+    for (i, j) in edges {
+        circuits.union(i, j);
+    }
+
+    // rank circuits by size so the same input always gets the same colours
+    let mut roots = (0..len)
+        .filter(|i| circuits.find(*i) == *i && circuits.size_of(*i) > 1)
+        .collect::<Vec<_>>();
+    roots.sort_by_key(|root| std::cmp::Reverse(circuits.size_of(*root)));
+    let mut colour_of_root = vec![LONELY; len];
+    for (rank, root) in roots.into_iter().enumerate() {
+        colour_of_root[root] = PALETTE[rank % PALETTE.len()];
+    }
+
+    (0..len).map(|i| colour_of_root[circuits.find(i)]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day8::tests::EXAMPLE;
+
+    #[test]
+    fn test_export_ply_example() {
+        let boxes = Pos::<3>::from_input(EXAMPLE);
+        let mut out = vec![];
+        export_scene(
+            &mut out,
+            &boxes,
+            10,
+            Metric::Euclidean,
+            TieBreak::Lexicographic,
+            SceneFormat::Ply,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let (header, body) = out.split_once("end_header\n").unwrap();
+        assert!(header.contains("element vertex 20\n"));
+        assert!(header.contains("element edge 10\n"));
+        let lines = body.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 30);
+        // 162,817,812 and 425,690,689 are the closest pair, in the circuit of 4
+        assert_eq!(lines[0], "162 817 812 60 180 75");
+        assert_eq!(lines[20], "0 19");
+    }
+
+    #[test]
+    fn test_export_obj_example() {
+        let boxes = Pos::<3>::from_input(EXAMPLE);
+        let mut out = vec![];
+        export_scene(
+            &mut out,
+            &boxes,
+            10,
+            Metric::Euclidean,
+            TieBreak::Lexicographic,
+            SceneFormat::Obj,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(out.lines().filter(|l| l.starts_with("v ")).count(), 20);
+        assert_eq!(out.lines().filter(|l| l.starts_with("l ")).count(), 10);
+        assert!(out.contains("\nl 1 20\n"));
+    }
+}
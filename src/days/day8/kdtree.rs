@@ -0,0 +1,220 @@
+use std::{borrow::Cow, collections::BinaryHeap};
+
+use super::{Metric, Pos};
+
+// Static k-d tree over the junction boxes.
+// The tree is implicit: `order` holds box indices and every subtree is a range of it, split on
+// its middle element, cycling through the axes as we go deeper. A node is identified by the
+// position of its middle element, which lets callers keep per-node data in a plain Vec.
+// The boxes are usually borrowed, but a tree can own them when it has to outlive its input.
+pub struct KdTree<'a, const D: usize = 3> {
+    boxes: Cow<'a, [Pos<D>]>,
+    metric: Metric,
+    order: Vec<usize>,
+}
+
+impl<'a, const D: usize> KdTree<'a, D> {
+    pub fn new(boxes: &'a [Pos<D>], metric: Metric) -> Self {
+        Self::from_cow(Cow::Borrowed(boxes), metric)
+    }
+
+    pub fn from_owned(boxes: Vec<Pos<D>>, metric: Metric) -> KdTree<'static, D> {
+        KdTree::from_cow(Cow::Owned(boxes), metric)
+    }
+
+    fn from_cow(boxes: Cow<'a, [Pos<D>]>, metric: Metric) -> Self {
+        let mut order = (0..boxes.len()).collect::<Vec<_>>();
+        build(&boxes, &mut order, 0);
+        Self {
+            boxes,
+            metric,
+            order,
+        }
+    }
+
+    pub fn boxes(&self) -> &[Pos<D>] {
+        &self.boxes
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    // Closest box to `target` as (index, distance), lowest index first on ties
+    pub fn nearest(&self, target: &Pos<D>) -> Option<(usize, i128)> {
+        let mut best: Option<(usize, i128)> = None;
+        self.visit_within(target, None, &|_| false, &mut |i, distance, radius| {
+            if best.is_none_or(|(b, b_distance)| (distance, i) < (b_distance, b)) {
+                best = Some((i, distance));
+                *radius = Some(distance);
+            }
+        });
+        best
+    }
+
+    // The `k` closest boxes to `target` as (index, distance), closest first
+    pub fn k_nearest(&self, target: &Pos<D>, k: usize) -> Vec<(usize, i128)> {
+        if k == 0 {
+            return vec![];
+        }
+
+        let mut heap = BinaryHeap::<(i128, usize)>::with_capacity(k + 1);
+        self.visit_within(target, None, &|_| false, &mut |i, distance, radius| {
+            heap.push((distance, i));
+            if heap.len() > k {
+                heap.pop();
+            }
+            if heap.len() == k {
+                *radius = heap.peek().map(|(d, _)| *d);
+            }
+        });
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|(distance, i)| (i, distance))
+            .collect()
+    }
+
+    // Calls `visit` with every box at distance <= radius from `target` (all of them if there is
+    // no radius), nearest half of each subtree first. `visit` can shrink the radius to prune the
+    // rest of the search, and `skip` can discard a whole subtree by its node.
+    pub(super) fn visit_within(
+        &self,
+        target: &Pos<D>,
+        mut radius: Option<i128>,
+        skip: &dyn Fn(usize) -> bool,
+        visit: &mut dyn FnMut(usize, i128, &mut Option<i128>),
+    ) {
+        self.visit_node(target, 0, self.order.len(), 0, &mut radius, skip, visit);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn visit_node(
+        &self,
+        target: &Pos<D>,
+        start: usize,
+        end: usize,
+        depth: usize,
+        radius: &mut Option<i128>,
+        skip: &dyn Fn(usize) -> bool,
+        visit: &mut dyn FnMut(usize, i128, &mut Option<i128>),
+    ) {
+        if start >= end {
+            return;
+        }
+
+        let node = (start + end) / 2;
+        if skip(node) {
+            return;
+        }
+
+        let i = self.order[node];
+        let distance = self.metric.distance(target, &self.boxes[i]);
+        if radius.is_none_or(|r| distance <= r) {
+            visit(i, distance, radius);
+        }
+
+        let axis = depth % D;
+        let offset = target.coordinates[axis] as i128 - self.boxes[i].coordinates[axis] as i128;
+        let (near, far) = if offset < 0 {
+            ((start, node), (node + 1, end))
+        } else {
+            ((node + 1, end), (start, node))
+        };
+
+        self.visit_node(target, near.0, near.1, depth + 1, radius, skip, visit);
+        if radius.is_none_or(|r| self.metric.axis_distance(offset) <= r) {
+            self.visit_node(target, far.0, far.1, depth + 1, radius, skip, visit);
+        }
+    }
+
+    // Folds `value` bottom-up into a per-node label that is Some only when every box in the
+    // subtree has the same value
+    pub(super) fn uniform_labels(&self, value: &[usize]) -> Vec<Option<usize>> {
+        let mut labels = vec![None; self.order.len()];
+        self.label_node(0, self.order.len(), value, &mut labels);
+        labels
+    }
+
+    fn label_node(
+        &self,
+        start: usize,
+        end: usize,
+        value: &[usize],
+        labels: &mut Vec<Option<usize>>,
+    ) -> Option<Option<usize>> {
+        if start >= end {
+            // empty subtrees don't constrain their parent
+            return None;
+        }
+
+        let node = (start + end) / 2;
+        let own = Some(value[self.order[node]]);
+        let left = self.label_node(start, node, value, labels);
+        let right = self.label_node(node + 1, end, value, labels);
+
+        let label = [left, right]
+            .into_iter()
+            .flatten()
+            .fold(own, |acc, l| if acc == l { acc } else { None });
+        labels[node] = label;
+        Some(label)
+    }
+}
+
+fn build<const D: usize>(boxes: &[Pos<D>], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % D;
+    let middle = order.len() / 2;
+    order.select_nth_unstable_by_key(middle, |i| boxes[*i].coordinates[axis]);
+    let (left, right) = order.split_at_mut(middle);
+    build(boxes, left, depth + 1);
+    build(boxes, &mut right[1..], depth + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day8::tests::EXAMPLE;
+
+    #[test]
+    fn test_nearest_matches_brute_force() {
+        let boxes = Pos::<3>::from_input(EXAMPLE);
+        let tree = KdTree::new(&boxes, Metric::Euclidean);
+        let target = Pos {
+            coordinates: [500, 500, 500],
+        };
+
+        let expected = (0..boxes.len())
+            .map(|i| (Metric::Euclidean.distance(&target, &boxes[i]), i))
+            .min()
+            .map(|(distance, i)| (i, distance));
+        assert_eq!(tree.nearest(&target), expected);
+    }
+
+    #[test]
+    fn test_k_nearest_matches_brute_force() {
+        let boxes = Pos::<3>::from_input(EXAMPLE);
+        let tree = KdTree::new(&boxes, Metric::Euclidean);
+
+        for target in &boxes {
+            let mut expected = (0..boxes.len())
+                .map(|i| (Metric::Euclidean.distance(target, &boxes[i]), i))
+                .collect::<Vec<_>>();
+            expected.sort();
+            let expected = expected
+                .into_iter()
+                .take(4)
+                .map(|(distance, i)| (i, distance))
+                .collect::<Vec<_>>();
+            assert_eq!(tree.k_nearest(target, 4), expected);
+        }
+    }
+}
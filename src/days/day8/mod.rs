@@ -1,151 +1,486 @@
 // https://adventofcode.com/2025/day/8
 
+pub mod dendrogram;
+pub mod export;
+pub mod kdtree;
+pub mod online;
+
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use kdtree::KdTree;
+
+use crate::input::normalize;
+
 pub fn solution_part1(input: &str, connections: usize) -> usize {
-    let boxes = Pos::from_input(input);
+    solution_part1_with::<3>(
+        input,
+        connections,
+        Metric::Euclidean,
+        TieBreak::Lexicographic,
+    )
+}
+
+pub fn solution_part2(input: &str) -> usize {
+    solution_part2_with::<3>(input, Metric::Euclidean, TieBreak::Lexicographic)
+        .try_into()
+        .unwrap()
+}
+
+pub fn solution_part1_with<const D: usize>(
+    input: &str,
+    connections: usize,
+    metric: Metric,
+    tie_break: TieBreak,
+) -> usize {
+    let boxes = Pos::<D>::from_input(input);
 
-    let mut distances = Vec::<((usize, usize), usize)>::new();
-    for i in 0..boxes.len() - 1 {
-        for j in i + 1..boxes.len() {
-            distances.push(((i, j), boxes[i].distance(&boxes[j])));
+    let mut circuits = DisjointSet::new(boxes.len());
+    for connection in closest_connections(&boxes, connections, metric, tie_break) {
+        let (i, j) = connection.boxes;
+        circuits.union(i, j);
+    }
+
+    let mut sizes = circuits.circuit_sizes();
+    sizes.sort_unstable();
+    sizes.into_iter().rev().take(3).product()
+}
+
+// Product of the first coordinates of the last two boxes connected
+pub fn solution_part2_with<const D: usize>(
+    input: &str,
+    metric: Metric,
+    tie_break: TieBreak,
+) -> i128 {
+    let boxes = Pos::<D>::from_input(input);
+    let last_connection = minimum_spanning_tree(&boxes, metric, tie_break)
+        .pop()
+        .unwrap();
+    let (i, j) = last_connection.boxes;
+
+    boxes[i].coordinates[0] as i128 * boxes[j].coordinates[0] as i128
+}
+
+// A pair of boxes (lowest index first) and their distance.
+// Ordered by distance, then by the pair of indexes, which is TieBreak::Lexicographic.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Connection {
+    pub distance: i128,
+    pub boxes: (usize, usize),
+}
+
+impl Connection {
+    fn new(i: usize, j: usize, distance: i128) -> Self {
+        Self {
+            distance,
+            boxes: (i.min(j), i.max(j)),
         }
     }
+}
+
+// Which connection goes first between two at the same distance.
+// A custom order must be total and consistent, the spanning tree relies on it.
+#[derive(Clone, Copy, Default, Debug)]
+pub enum TieBreak {
+    // lowest pair of indexes first, i.e. in the order the boxes are listed in the input
+    #[default]
+    Lexicographic,
+    // highest pair of indexes first
+    ReverseLexicographic,
+    Custom(fn(&(usize, usize), &(usize, usize)) -> Ordering),
+}
 
-    distances.select_nth_unstable_by(connections - 1, |a, b| a.1.cmp(&b.1));
-    let closest_boxes = distances[..connections].into_iter().map(|d| d.0);
+impl TieBreak {
+    pub fn cmp(&self, a: &Connection, b: &Connection) -> Ordering {
+        a.distance.cmp(&b.distance).then_with(|| match self {
+            TieBreak::Lexicographic => a.boxes.cmp(&b.boxes),
+            TieBreak::ReverseLexicographic => b.boxes.cmp(&a.boxes),
+            TieBreak::Custom(cmp) => cmp(&a.boxes, &b.boxes),
+        })
+    }
+}
 
-    let mut circuit_connections = vec![0usize; boxes.len()];
-    let mut circuits: Vec<Vec<usize>> = vec![];
-    for (i, j) in closest_boxes {
-        let j_n = circuit_connections[j];
-        let i_n = circuit_connections[i];
+impl TieBreak {
+    // Connections joining boxes all in the same place (sorted indexes), the ones picked first
+    // among their pairs, which are all at distance 0
+    fn connect_duplicates(&self, group: &[usize]) -> Vec<Connection> {
+        match self {
+            TieBreak::Lexicographic => group[1..]
+                .iter()
+                .map(|j| Connection::new(group[0], *j, 0))
+                .collect(),
+            TieBreak::ReverseLexicographic => {
+                let (last, rest) = group.split_last().unwrap();
+                rest.iter().map(|i| Connection::new(*i, *last, 0)).collect()
+            }
+            TieBreak::Custom(_) => {
+                let mut pairs = (0..group.len())
+                    .flat_map(|i| (i + 1..group.len()).map(move |j| (i, j)))
+                    .collect::<Vec<_>>();
+                pairs.sort_by(|a, b| {
+                    self.cmp(
+                        &Connection::new(group[a.0], group[a.1], 0),
+                        &Connection::new(group[b.0], group[b.1], 0),
+                    )
+                });
 
-        if i_n == 0 && j_n == 0 {
-            circuits.push(vec![i, j]);
-            circuit_connections[i] = circuits.len();
-            circuit_connections[j] = circuits.len();
-            continue;
+                let mut circuits = DisjointSet::new(group.len());
+                pairs
+                    .into_iter()
+                    .filter(|(i, j)| circuits.union(*i, *j))
+                    .map(|(i, j)| Connection::new(group[i], group[j], 0))
+                    .collect()
+            }
         }
+    }
 
-        if i_n == 0 {
-            circuit_connections[i] = j_n;
-            circuits[j_n - 1].push(i);
-            continue;
+    // The pair picked first between boxes all in one place and boxes all in another (sorted
+    // indexes), every pair being at the same distance. A custom order has to try them all.
+    fn closest_pair(&self, a: &[usize], b: &[usize]) -> (usize, usize) {
+        match self {
+            TieBreak::Lexicographic => (a[0], b[0]),
+            TieBreak::ReverseLexicographic => (a[a.len() - 1], b[b.len() - 1]),
+            TieBreak::Custom(cmp) => a
+                .iter()
+                .flat_map(|i| b.iter().map(move |j| (*i.min(j), *i.max(j))))
+                .min_by(cmp)
+                .unwrap(),
         }
+    }
+}
+
+// Connection ordered by a TieBreak, to keep them in a BinaryHeap
+struct Ranked(Connection, TieBreak);
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.1.cmp(&self.0, &other.0)
+    }
+}
+
+// Pairs at the same distance as the last of the closest `k`, when some of them made the cut and
+// some didn't: the ones kept depend entirely on the TieBreak.
+#[derive(PartialEq, Eq, Debug)]
+pub struct TieReport {
+    pub distance: i128,
+    pub included: Vec<Connection>,
+    pub excluded: Vec<Connection>,
+}
+
+pub fn ties_at_boundary<const D: usize>(
+    boxes: &[Pos<D>],
+    k: usize,
+    metric: Metric,
+    tie_break: TieBreak,
+) -> Option<TieReport> {
+    let included = closest_connections(boxes, k, metric, tie_break);
+    let distance = included.last()?.distance;
+
+    let tree = KdTree::new(boxes, metric);
+    let mut tied = vec![];
+    for (i, pos) in boxes.iter().enumerate() {
+        tree.visit_within(pos, Some(distance), &|_| false, &mut |j, d, _| {
+            if i < j && d == distance {
+                tied.push(Connection::new(i, j, d));
+            }
+        });
+    }
+    tied.sort_by(|a, b| tie_break.cmp(a, b));
+
+    let (included, excluded): (Vec<_>, Vec<_>) = tied
+        .into_iter()
+        .partition(|c| included.iter().any(|i| i.boxes == c.boxes));
+    if excluded.is_empty() || included.is_empty() {
+        return None;
+    }
+
+    Some(TieReport {
+        distance,
+        included,
+        excluded,
+    })
+}
+
+// The `k` closest pairs of boxes, closest first.
+// Pairs at the same distance are taken in `tie_break` order, so the result doesn't depend on how
+// the search happens to visit them. Keeps the best k so far in a
+// max-heap and uses the worst of them as search radius for the next boxes, so memory is O(n + k).
+pub fn closest_connections<const D: usize>(
+    boxes: &[Pos<D>],
+    k: usize,
+    metric: Metric,
+    tie_break: TieBreak,
+) -> Vec<Connection> {
+    if k == 0 {
+        return vec![];
+    }
+
+    let tree = KdTree::new(boxes, metric);
+    let mut heap = BinaryHeap::<Ranked>::with_capacity(k + 1);
+    for (i, pos) in boxes.iter().enumerate() {
+        let radius = if heap.len() == k {
+            heap.peek().map(|c| c.0.distance)
+        } else {
+            None
+        };
+
+        tree.visit_within(pos, radius, &|_| false, &mut |j, distance, radius| {
+            // every pair is seen from both ends, only keep it from the lowest one
+            if j <= i {
+                return;
+            }
+
+            let candidate = Ranked(Connection::new(i, j, distance), tie_break);
+            if heap.len() == k {
+                if candidate >= *heap.peek().unwrap() {
+                    return;
+                }
+                heap.pop();
+            }
+            heap.push(candidate);
+
+            if heap.len() == k {
+                *radius = heap.peek().map(|c| c.0.distance);
+            }
+        });
+    }
+
+    heap.into_sorted_vec().into_iter().map(|c| c.0).collect()
+}
+
+// Position of `connection` in the closest-first order (1 for the closest pair), counting the
+// pairs within its distance with the k-d tree instead of listing them all
+pub fn connection_number<const D: usize>(
+    boxes: &[Pos<D>],
+    connection: &Connection,
+    metric: Metric,
+    tie_break: TieBreak,
+) -> usize {
+    let tree = KdTree::new(boxes, metric);
+    let mut count = 0;
+    for (i, pos) in boxes.iter().enumerate() {
+        tree.visit_within(
+            pos,
+            Some(connection.distance),
+            &|_| false,
+            &mut |j, distance, _| {
+                if i < j
+                    && tie_break.cmp(&Connection::new(i, j, distance), connection)
+                        != Ordering::Greater
+                {
+                    count += 1;
+                }
+            },
+        );
+    }
+
+    count
+}
+
+// Connections joining all the boxes in one circuit, in the order they'd be made by connecting
+// the closest boxes first. Euclidean MST using Borůvka's algorithm: every round each circuit
+// finds its closest box outside of itself with the k-d tree, skipping subtrees that lie entirely
+// in the same circuit, so we never materialise all the pairs.
+// Boxes in the same place connect first, at distance 0, and are all as close to anything else,
+// so the rounds only go over one box per place: ties between duplicates can't prune the search.
+pub fn minimum_spanning_tree<const D: usize>(
+    boxes: &[Pos<D>],
+    metric: Metric,
+    tie_break: TieBreak,
+) -> Vec<Connection> {
+    let mut order = (0..boxes.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|i| (boxes[*i].coordinates, *i));
+    let groups = order
+        .chunk_by(|i, j| boxes[*i] == boxes[*j])
+        .collect::<Vec<_>>();
+    let places = groups.iter().map(|g| boxes[g[0]]).collect::<Vec<_>>();
+
+    let mut connections = Vec::with_capacity(boxes.len().saturating_sub(1));
+    for group in &groups {
+        connections.extend(tie_break.connect_duplicates(group));
+    }
+
+    let tree = KdTree::new(&places, metric);
+    let mut circuits = DisjointSet::new(places.len());
+    let mut joined = 0;
+
+    while joined + 1 < places.len() {
+        let circuit = (0..places.len())
+            .map(|i| circuits.find(i))
+            .collect::<Vec<_>>();
+        let labels = tree.uniform_labels(&circuit);
+        // best connection out of every circuit, with the places it joins
+        let mut closest: Vec<Option<(Connection, (usize, usize))>> = vec![None; places.len()];
 
-        if j_n == 0 {
-            circuit_connections[j] = i_n;
-            circuits[i_n - 1].push(j);
-            continue;
+        for (i, pos) in places.iter().enumerate() {
+            let c = circuit[i];
+            let mut best = closest[c];
+            tree.visit_within(
+                pos,
+                best.map(|b| b.0.distance),
+                &|node| labels[node] == Some(c),
+                &mut |j, distance, radius| {
+                    if circuit[j] == c {
+                        return;
+                    }
+                    let (a, b) = tie_break.closest_pair(groups[i], groups[j]);
+                    let candidate = Connection::new(a, b, distance);
+                    if best.is_none_or(|b| tie_break.cmp(&candidate, &b.0) == Ordering::Less) {
+                        best = Some((candidate, (i, j)));
+                        *radius = Some(distance);
+                    }
+                },
+            );
+            closest[c] = best;
         }
 
-        if i_n != j_n {
-            // move all circuits from j's to i's
-            let [circuit_j, circuit_i] = circuits.get_disjoint_mut([j_n - 1, i_n - 1]).unwrap();
-            let to_move = circuit_j.drain(..);
-            for j_neighbour in to_move {
-                circuit_connections[j_neighbour] = i_n;
-                circuit_i.push(j_neighbour)
+        for (connection, (i, j)) in closest.into_iter().flatten() {
+            if circuits.union(i, j) {
+                connections.push(connection);
+                joined += 1;
             }
         }
     }
 
-    circuits.sort_by_key(|v| v.len());
-    let out = circuits
-        .into_iter()
-        .rev()
-        .map(|v| v.len())
-        .take(3)
-        .product();
+    connections.sort_by(|a, b| tie_break.cmp(a, b));
+    connections
+}
 
-    out
+// Union-find over box indexes, with path halving and union by size
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let boxes = Pos::from_input(input);
+impl DisjointSet {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
 
-    let mut distances = Vec::<((usize, usize), usize)>::new();
-    for i in 0..boxes.len() - 1 {
-        for j in i + 1..boxes.len() {
-            distances.push(((i, j), boxes[i].distance(&boxes[j])));
+    pub fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
         }
+        i
     }
 
-    distances.sort_by_key(|d| d.1);
-    let closest_boxes = distances.into_iter().map(|d| d.0);
-
-    let mut circuit_connections = vec![0usize; boxes.len()];
-    let mut circuits: Vec<Vec<usize>> = vec![];
-    for (i, j) in closest_boxes {
-        let j_n = circuit_connections[j];
-        let i_n = circuit_connections[i];
-
-        if i_n == 0 && j_n == 0 {
-            circuits.push(vec![i, j]);
-            circuit_connections[i] = circuits.len();
-            circuit_connections[j] = circuits.len();
-        } else if i_n == 0 {
-            circuit_connections[i] = j_n;
-            circuits[j_n - 1].push(i);
-        } else if j_n == 0 {
-            circuit_connections[j] = i_n;
-            circuits[i_n - 1].push(j);
-        } else if i_n != j_n {
-            // move all circuits from j's to i's
-            let [circuit_j, circuit_i] = circuits.get_disjoint_mut([j_n - 1, i_n - 1]).unwrap();
-            let to_move = circuit_j.drain(..);
-            for j_neighbour in to_move {
-                circuit_connections[j_neighbour] = i_n;
-                circuit_i.push(j_neighbour)
-            }
-        } else {
-            continue;
+    // false if they were already in the same set
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (mut i, mut j) = (self.find(i), self.find(j));
+        if i == j {
+            return false;
         }
 
-        if i_n != 0 && circuits[i_n - 1].len() == boxes.len()
-            || j_n != 0 && circuits[j_n - 1].len() == boxes.len()
-        {
-            return boxes[i].x * boxes[j].x;
+        if self.size[i] < self.size[j] {
+            std::mem::swap(&mut i, &mut j);
         }
+        self.parent[j] = i;
+        self.size[i] += self.size[j];
+        true
     }
 
-    unreachable!()
+    // size of every set, one entry per set
+    pub fn circuit_sizes(&self) -> Vec<usize> {
+        (0..self.parent.len())
+            .filter(|i| self.parent[*i] == *i)
+            .map(|i| self.size[i])
+            .collect()
+    }
+
+    // adds a new set with a single element, returning it
+    pub fn push(&mut self) -> usize {
+        let i = self.parent.len();
+        self.parent.push(i);
+        self.size.push(1);
+        i
+    }
+
+    pub fn size_of(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.size[root]
+    }
 }
 
-struct Pos {
-    x: usize,
-    y: usize,
-    z: usize,
+// A junction box, 3D for the puzzle but any number of dimensions works
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Pos<const D: usize = 3> {
+    pub coordinates: [i64; D],
 }
 
-impl Pos {
-    fn from_input(input: &str) -> Vec<Self> {
-        input
+impl<const D: usize> Pos<D> {
+    pub fn from_input(input: &str) -> Vec<Self> {
+        normalize(input)
             .trim()
             .lines()
             .map(|line| {
-                let mut coordinates = line.splitn(3, ",");
+                let mut coordinates = line.split(",");
                 Self {
-                    x: coordinates.next().unwrap().parse::<usize>().unwrap(),
-                    y: coordinates.next().unwrap().parse::<usize>().unwrap(),
-                    z: coordinates.next().unwrap().parse::<usize>().unwrap(),
+                    coordinates: std::array::from_fn(|_| {
+                        coordinates.next().unwrap().trim().parse::<i64>().unwrap()
+                    }),
                 }
             })
             .collect::<Vec<Self>>()
     }
+}
 
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum Metric {
     // Not square rooted because we only care about relative distance
-    fn distance(&self, pos: &Pos) -> usize {
-        (self.x as isize - pos.x as isize).pow(2) as usize
-            + (self.y as isize - pos.y as isize).pow(2) as usize
-            + (self.z as isize - pos.z as isize).pow(2) as usize
+    #[default]
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+impl Metric {
+    // Offsets between i64 coordinates fit an i128, but their squares don't once they're more
+    // than about 2^63 apart, so distances saturate at i128::MAX: boxes that far apart all tie
+    // and go in TieBreak order
+    pub fn distance<const D: usize>(&self, a: &Pos<D>, b: &Pos<D>) -> i128 {
+        let offsets =
+            (0..D).map(|axis| (a.coordinates[axis] as i128 - b.coordinates[axis] as i128).abs());
+        match self {
+            Metric::Euclidean => offsets
+                .map(|offset| offset.saturating_mul(offset))
+                .fold(0, i128::saturating_add),
+            Metric::Manhattan => offsets.fold(0, i128::saturating_add),
+            Metric::Chebyshev => offsets.max().unwrap_or(0),
+        }
+    }
+
+    // Lower bound of the distance to anything `offset` away along a single axis
+    fn axis_distance(&self, offset: i128) -> i128 {
+        match self {
+            Metric::Euclidean => offset.saturating_mul(offset),
+            Metric::Manhattan | Metric::Chebyshev => offset.abs(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
-    const EXAMPLE: &str = "162,817,812
+    pub(super) const EXAMPLE: &str = "162,817,812
 57,618,57
 906,360,560
 592,479,940
@@ -178,11 +513,152 @@ mod tests {
         assert_eq!(solution_part1(INPUT, 1000), 352584);
     }
 
+    #[test]
+    fn test_closest_connections_example() {
+        let boxes = Pos::<3>::from_input(EXAMPLE);
+        let mut expected = vec![];
+        for i in 0..boxes.len() {
+            for j in i + 1..boxes.len() {
+                expected.push(Connection::new(
+                    i,
+                    j,
+                    Metric::Euclidean.distance(&boxes[i], &boxes[j]),
+                ));
+            }
+        }
+        expected.sort();
+        expected.truncate(10);
+
+        assert_eq!(
+            closest_connections(&boxes, 10, Metric::Euclidean, TieBreak::Lexicographic),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree_metrics() {
+        let boxes = Pos::<2>::from_input("-5,3\n12,-7\n0,0\n-9,-9\n4,11\n7,-2\n-1,6");
+
+        for metric in [Metric::Euclidean, Metric::Manhattan, Metric::Chebyshev] {
+            let mut all = vec![];
+            for i in 0..boxes.len() {
+                for j in i + 1..boxes.len() {
+                    all.push(Connection::new(i, j, metric.distance(&boxes[i], &boxes[j])));
+                }
+            }
+            all.sort();
+
+            let mut circuits = DisjointSet::new(boxes.len());
+            let expected = all
+                .into_iter()
+                .filter(|c| circuits.union(c.boxes.0, c.boxes.1))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                minimum_spanning_tree(&boxes, metric, TieBreak::Lexicographic),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_distances_far_apart() {
+        let boxes = Pos::<3>::from_input(&format!(
+            "{min},0,0\n{max},0,0\n0,{min},{max}\n0,0,0",
+            min = i64::MIN,
+            max = i64::MAX
+        ));
+        assert_eq!(Metric::Euclidean.distance(&boxes[0], &boxes[1]), i128::MAX);
+        assert_eq!(Metric::Euclidean.distance(&boxes[0], &boxes[2]), i128::MAX);
+        assert_eq!(
+            Metric::Manhattan.distance(&boxes[0], &boxes[2]),
+            3 * (1 << 63) - 1
+        );
+        assert_eq!(
+            Metric::Chebyshev.distance(&boxes[0], &boxes[1]),
+            u64::MAX as i128
+        );
+
+        // pairs past i128::MAX tie, but they still get connected
+        let connections =
+            closest_connections(&boxes, 6, Metric::Euclidean, TieBreak::Lexicographic);
+        assert_eq!(connections.len(), 6);
+        assert_eq!(
+            minimum_spanning_tree(&boxes, Metric::Euclidean, TieBreak::Lexicographic).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree_duplicates() {
+        let boxes = Pos::<2>::from_input("1,1\n0,0\n1,1\n3,0\n0,0\n1,1\n3,0\n0,2\n0,0");
+        let by_second: fn(&(usize, usize), &(usize, usize)) -> Ordering =
+            |a, b| (a.1, a.0).cmp(&(b.1, b.0));
+
+        for tie_break in [
+            TieBreak::Lexicographic,
+            TieBreak::ReverseLexicographic,
+            TieBreak::Custom(by_second),
+        ] {
+            let mut all = vec![];
+            for i in 0..boxes.len() {
+                for j in i + 1..boxes.len() {
+                    all.push(Connection::new(
+                        i,
+                        j,
+                        Metric::Euclidean.distance(&boxes[i], &boxes[j]),
+                    ));
+                }
+            }
+            all.sort_by(|a, b| tie_break.cmp(a, b));
+
+            let mut circuits = DisjointSet::new(boxes.len());
+            let expected = all
+                .into_iter()
+                .filter(|c| circuits.union(c.boxes.0, c.boxes.1))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                minimum_spanning_tree(&boxes, Metric::Euclidean, tie_break),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_ties_at_boundary() {
+        let boxes = Pos::<2>::from_input("0,0\n1,0\n2,0\n3,0\n10,10");
+
+        let report = ties_at_boundary(&boxes, 2, Metric::Euclidean, TieBreak::Lexicographic);
+        assert_eq!(
+            report,
+            Some(TieReport {
+                distance: 1,
+                included: vec![Connection::new(0, 1, 1), Connection::new(1, 2, 1)],
+                excluded: vec![Connection::new(2, 3, 1)],
+            })
+        );
+
+        let report = ties_at_boundary(&boxes, 2, Metric::Euclidean, TieBreak::ReverseLexicographic);
+        assert_eq!(report.unwrap().excluded, vec![Connection::new(0, 1, 1)]);
+
+        assert_eq!(
+            ties_at_boundary(&boxes, 3, Metric::Euclidean, TieBreak::Lexicographic),
+            None
+        );
+    }
+
     #[test]
     fn test_part2_example() {
         assert_eq!(solution_part2(EXAMPLE), 25272);
     }
 
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input, 10), 40);
+            assert_eq!(solution_part2(&input), 25272);
+        }
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(solution_part2(INPUT), 9617397716);
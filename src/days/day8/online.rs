@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+
+use super::{Connection, DisjointSet, Metric, Pos, kdtree::KdTree};
+
+// Circuits that grow one box at a time: every new box is connected to all the boxes already
+// there within `threshold` (in the metric's units, so squared for Euclidean), which ends up with
+// the same circuits as Dendrogram::circuits_within on the whole input.
+// The boxes are kept in k-d trees of power of two sizes, merged like a binary counter when two
+// of them have the same size, so an insertion never rebuilds more than it has to.
+pub struct OnlineCircuits<const D: usize = 3> {
+    metric: Metric,
+    threshold: i128,
+    trees: Vec<Block<D>>,
+    circuits: DisjointSet,
+    // how many circuits there are of each size
+    sizes: BTreeMap<usize, usize>,
+    len: usize,
+}
+
+struct Block<const D: usize> {
+    tree: KdTree<'static, D>,
+    // index of every box of the tree in insertion order
+    ids: Vec<usize>,
+}
+
+impl<const D: usize> OnlineCircuits<D> {
+    pub fn new(metric: Metric, threshold: i128) -> Self {
+        Self {
+            metric,
+            threshold,
+            trees: vec![],
+            circuits: DisjointSet::new(0),
+            sizes: BTreeMap::new(),
+            len: 0,
+        }
+    }
+
+    // Adds a box and returns the connections it made, closest first
+    pub fn insert(&mut self, pos: Pos<D>) -> Vec<Connection> {
+        let id = self.circuits.push();
+        self.len += 1;
+        *self.sizes.entry(1).or_default() += 1;
+
+        let mut connections = vec![];
+        for block in &self.trees {
+            block.tree.visit_within(
+                &pos,
+                Some(self.threshold),
+                &|_| false,
+                &mut |i, distance, _| {
+                    connections.push(Connection::new(block.ids[i], id, distance));
+                },
+            );
+        }
+        connections.sort();
+
+        for connection in &connections {
+            let (i, j) = connection.boxes;
+            let (size_i, size_j) = (self.circuits.size_of(i), self.circuits.size_of(j));
+            if self.circuits.union(i, j) {
+                self.remove_size(size_i);
+                self.remove_size(size_j);
+                *self.sizes.entry(size_i + size_j).or_default() += 1;
+            }
+        }
+
+        self.push_block(vec![pos], vec![id]);
+        connections
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn circuit_count(&self) -> usize {
+        self.sizes.values().sum()
+    }
+
+    // Sizes of the `k` largest circuits, largest first
+    pub fn largest_circuits(&self, k: usize) -> Vec<usize> {
+        self.sizes
+            .iter()
+            .rev()
+            .flat_map(|(size, count)| std::iter::repeat_n(*size, *count))
+            .take(k)
+            .collect()
+    }
+
+    pub fn circuit_size_of(&mut self, box_index: usize) -> usize {
+        self.circuits.size_of(box_index)
+    }
+
+    pub fn same_circuit(&mut self, a: usize, b: usize) -> bool {
+        self.circuits.find(a) == self.circuits.find(b)
+    }
+
+    fn remove_size(&mut self, size: usize) {
+        let count = self.sizes.get_mut(&size).unwrap();
+        *count -= 1;
+        if *count == 0 {
+            self.sizes.remove(&size);
+        }
+    }
+
+    fn push_block(&mut self, mut boxes: Vec<Pos<D>>, mut ids: Vec<usize>) {
+        while self.trees.last().is_some_and(|b| b.ids.len() == ids.len()) {
+            let block = self.trees.pop().unwrap();
+            boxes.extend_from_slice(block.tree.boxes());
+            ids.extend(block.ids);
+        }
+
+        self.trees.push(Block {
+            tree: KdTree::from_owned(boxes, self.metric),
+            ids,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day8::{TieBreak, dendrogram::Dendrogram, tests::EXAMPLE};
+
+    #[test]
+    fn test_online_matches_threshold_example() {
+        let boxes = Pos::<3>::from_input(EXAMPLE);
+        let dendrogram = Dendrogram::new(&boxes, Metric::Euclidean, TieBreak::Lexicographic);
+        let threshold = dendrogram.threshold_for(5).unwrap();
+
+        let mut online = OnlineCircuits::new(Metric::Euclidean, threshold);
+        for (i, pos) in boxes.iter().enumerate() {
+            online.insert(*pos);
+
+            let expected =
+                Dendrogram::new(&boxes[..=i], Metric::Euclidean, TieBreak::Lexicographic)
+                    .circuits_within(threshold)
+                    .iter()
+                    .map(|c| c.len())
+                    .collect::<Vec<_>>();
+            assert_eq!(online.circuit_count(), expected.len());
+            assert_eq!(online.largest_circuits(boxes.len()), expected);
+        }
+        assert_eq!(online.len(), boxes.len());
+        assert_eq!(online.circuit_count(), 5);
+    }
+}
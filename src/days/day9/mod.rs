@@ -3,6 +3,8 @@
 use std::io::Write;
 use std::{collections::HashSet, fmt::Display, fs::OpenOptions};
 
+use crate::input::normalize;
+
 pub fn solution_part1(input: &str) -> usize {
     let mut min_distance_tr = usize::MAX;
     let mut min_tr = Pos { x: 0, y: 0 };
@@ -87,7 +89,7 @@ struct Map {
 impl Map {
     fn from_input(input: &str) -> Self {
         let mut width = 0;
-        let positions = input
+        let positions = normalize(input)
             .trim()
             .lines()
             .map(|l| {
@@ -124,6 +126,7 @@ impl Display for Pos {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::variants;
 
     const EXAMPLE: &str = "7,1
 11,1
@@ -151,6 +154,13 @@ mod tests {
         assert_eq!(solution_part2(EXAMPLE), 24);
     }
 
+    #[test]
+    fn test_input_variants() {
+        for input in variants(EXAMPLE) {
+            assert_eq!(solution_part1(&input), 50);
+        }
+    }
+
     #[test]
     fn test_part2_real() {
         assert_eq!(solution_part2(INPUT), 9999999999999);
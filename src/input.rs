@@ -0,0 +1,54 @@
+use std::borrow::Cow;
+
+// Puzzle input the way every parser expects it, whatever saved it: no UTF-8 byte order mark, `\n`
+// line endings, no whitespace at the end of lines and no blank lines at the end.
+// Already clean inputs, the usual case, are given back as they are.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let clean = !input.contains('\r')
+        && input.trim_end().len() == input.len()
+        && input
+            .lines()
+            .all(|line| line.trim_end().len() == line.len());
+    if clean {
+        return Cow::Borrowed(input);
+    }
+
+    let mut normalized = input
+        .lines()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    normalized.truncate(normalized.trim_end().len());
+    Cow::Owned(normalized)
+}
+
+// The ways an example can get saved, to check a day's parsers go through normalize
+#[cfg(test)]
+pub fn variants(example: &str) -> Vec<String> {
+    let crlf = example.replace('\n', "\r\n");
+    vec![
+        format!("{crlf}\r\n"),
+        format!("\u{feff}{example}"),
+        format!("\u{feff}{crlf}\r\n\r\n"),
+        example.replace('\n', " \t\n") + "  \n\n \n",
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(
+            normalize("a\nb c\n\nd"),
+            Cow::Borrowed("a\nb c\n\nd")
+        ));
+        for input in variants("a\nb c\n\nd") {
+            assert_eq!(normalize(&input), "a\nb c\n\nd");
+        }
+        assert_eq!(normalize(" a  \n  b\n"), " a\n  b");
+        assert_eq!(normalize("\u{feff}\r\n \n"), "");
+    }
+}
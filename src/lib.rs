@@ -1 +1,3 @@
+pub mod automaton;
 pub mod days;
+pub mod input;